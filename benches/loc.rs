@@ -0,0 +1,59 @@
+//! Measures the cost of `loc` on results that already contain an `EzError`
+//! and of `eget_opt` compared to `eget(...).ok()` on missing indices, as well
+//! as `eget_const` compared to `eget` with a literal index.
+//!
+//! Run with `cargo bench --bench loc`.
+
+use ez_err::prelude::*;
+use std::hint::black_box;
+use std::time::Instant;
+
+const ITERATIONS: u32 = 1_000_000;
+
+#[inline(never)]
+fn fail() -> Result<u32> {
+    Err(EzError::message("failed"))
+}
+
+#[inline(never)]
+fn succeed() -> Result<u32> {
+    Ok(42)
+}
+
+fn bench(name: &str, mut f: impl FnMut()) {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    println!("{:<28} {:?}/iter", name, start.elapsed() / ITERATIONS);
+}
+
+fn main() {
+    bench("loc on Ok", || {
+        let _ = black_box(black_box(succeed()).loc(flc!()));
+    });
+    bench("loc on Err (1 frame)", || {
+        let _ = black_box(black_box(fail()).loc(flc!()));
+    });
+    bench("loc on Err (8 frames)", || {
+        let mut res = black_box(fail());
+        for _ in 0..8 {
+            res = res.loc(flc!());
+        }
+        let _ = black_box(res);
+    });
+
+    let values = [1u32, 2, 3];
+    bench("eget(..).ok() on miss", || {
+        let _ = black_box(black_box(&values[..]).eget(black_box(7)).ok());
+    });
+    bench("eget_opt on miss", || {
+        let _ = black_box(black_box(&values[..]).eget_opt(black_box(7)));
+    });
+    bench("eget(2) on array", || {
+        let _ = black_box(black_box(&values).eget(2));
+    });
+    bench("eget_const::<2>() on array", || {
+        let _ = black_box(black_box(&values).eget_const::<2>());
+    });
+}
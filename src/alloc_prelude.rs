@@ -0,0 +1,9 @@
+//! The types and macros of `alloc` that are part of the `std` prelude, so
+//! that the crate can use them the same way in `no_std` mode.
+
+pub(crate) use alloc::borrow::ToOwned;
+pub(crate) use alloc::boxed::Box;
+pub(crate) use alloc::format;
+pub(crate) use alloc::string::{String, ToString};
+pub(crate) use alloc::vec;
+pub(crate) use alloc::vec::Vec;
@@ -0,0 +1,16 @@
+//! Conversion into [`anyhow::Error`] for the `anyhow` feature.
+//!
+//! [`EzError`] does not implement [`std::error::Error`], since that would
+//! conflict with its conversion from every other error type, so the blanket
+//! conversion of `anyhow` does not apply. The error is wrapped as a message
+//! instead and can be recovered with [`anyhow::Error::downcast`].
+
+use crate::core::EzError;
+
+/// Converts the error into an [`anyhow::Error`] whose display output is the
+/// full text produced by [`EzError::render`], including the stacktrace.
+impl From<EzError> for anyhow::Error {
+    fn from(err: EzError) -> anyhow::Error {
+        anyhow::Error::msg(err)
+    }
+}
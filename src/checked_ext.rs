@@ -0,0 +1,113 @@
+//! Integer arithmetic that returns errors instead of panicking or wrapping.
+
+use crate::core::*;
+use crate::flc;
+
+/// Extension trait for integers to divide, multiply, add and subtract
+/// without panicking on division by zero or overflow.
+///
+/// # Examples
+///
+/// ```
+/// # use ez_err::prelude::*;
+/// assert_eq!(Ok(4), 12i32.ediv(3));
+/// assert_eq!(&ErrorType::DivByZero, 12i32.ediv(0).unwrap_err().ty());
+/// assert_eq!(&ErrorType::ArithmeticOverflow, 200u8.eadd(100).unwrap_err().ty());
+/// ```
+pub trait CheckedExt: Sized {
+    /// Divides `self` by `rhs`, or returns [`ErrorType::DivByZero`] if `rhs`
+    /// is zero and [`ErrorType::ArithmeticOverflow`] if the result does not
+    /// fit, e.g. for `i32::MIN / -1`.
+    fn ediv(self, rhs: Self) -> Result<Self>;
+
+    /// Multiplies `self` by `rhs`, or returns
+    /// [`ErrorType::ArithmeticOverflow`] if the result does not fit.
+    fn emul(self, rhs: Self) -> Result<Self>;
+
+    /// Adds `rhs` to `self`, or returns [`ErrorType::ArithmeticOverflow`] if
+    /// the result does not fit.
+    fn eadd(self, rhs: Self) -> Result<Self>;
+
+    /// Subtracts `rhs` from `self`, or returns
+    /// [`ErrorType::ArithmeticOverflow`] if the result does not fit.
+    fn esub(self, rhs: Self) -> Result<Self>;
+}
+
+macro_rules! impl_checked_ext {
+    ($($ty:ty),*) => {
+        $(
+            impl CheckedExt for $ty {
+                #[inline]
+                fn ediv(self, rhs: Self) -> Result<Self> {
+                    if rhs == 0 {
+                        return Err(EzError::new(ErrorType::DivByZero)).loc(flc!());
+                    }
+                    overflow(self.checked_div(rhs)).loc(flc!())
+                }
+
+                #[inline]
+                fn emul(self, rhs: Self) -> Result<Self> {
+                    overflow(self.checked_mul(rhs)).loc(flc!())
+                }
+
+                #[inline]
+                fn eadd(self, rhs: Self) -> Result<Self> {
+                    overflow(self.checked_add(rhs)).loc(flc!())
+                }
+
+                #[inline]
+                fn esub(self, rhs: Self) -> Result<Self> {
+                    overflow(self.checked_sub(rhs)).loc(flc!())
+                }
+            }
+        )*
+    };
+}
+
+impl_checked_ext!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Returns the result of a checked operation or an
+/// [`ErrorType::ArithmeticOverflow`] error without a frame.
+#[inline]
+fn overflow<T>(value: Option<T>) -> Result<T> {
+    value.ok_or_else(|| EzError::new(ErrorType::ArithmeticOverflow))
+}
+
+#[cfg(all(test, not(feature = "panic_on_error")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn div_by_zero() {
+        assert_eq!(Ok(-4), (-12i32).ediv(3));
+        assert_eq!(Ok(2), 5u8.ediv(2));
+
+        for err in [7i32.ediv(0).unwrap_err(), 7u8.ediv(0).unwrap_err()] {
+            assert_eq!(&ErrorType::DivByZero, err.ty());
+            assert_eq!(1, err.frames().len());
+        }
+        assert_eq!(
+            &ErrorType::ArithmeticOverflow,
+            i32::MIN.ediv(-1).unwrap_err().ty()
+        );
+    }
+
+    #[test]
+    fn arithmetic_overflow() {
+        assert_eq!(Ok(255), 200u8.eadd(55));
+        assert_eq!(Ok(-6), 2i32.esub(8));
+        assert_eq!(Ok(i32::MAX - 1), (i32::MAX / 2).emul(2));
+
+        for err in [
+            200u8.eadd(56).unwrap_err(),
+            1u8.esub(2).unwrap_err(),
+            16u8.emul(16).unwrap_err(),
+            i32::MAX.eadd(1).unwrap_err(),
+            i32::MIN.esub(1).unwrap_err(),
+            i32::MAX.emul(2).unwrap_err(),
+        ] {
+            assert_eq!(&ErrorType::ArithmeticOverflow, err.ty());
+            assert_eq!(1, err.frames().len());
+        }
+    }
+}
@@ -0,0 +1,113 @@
+//! A small error type for hot or memory constrained code paths.
+
+use crate::core::*;
+
+/// A lightweight error that needs no allocation. It only stores a numeric
+/// code (see [`ErrorType::numeric_code`]), a static message and the origin
+/// frame, and can be copied freely.
+///
+/// Compared to [`EzError`] it loses dynamic messages (the message is always
+/// a `&'static str`, for converted errors the [`metric_label`]), the data of
+/// the error type (e.g. the index of [`ErrorType::IndexOutOfBounds`]) and
+/// every frame but the first one.
+///
+/// # Examples
+///
+/// ```
+/// # use ez_err::prelude::*;
+/// const EMPTY: CompactError = CompactError::new(1001, "queue was empty");
+///
+/// let err = EzError::from(EMPTY.at(flc!()));
+/// assert_eq!(1001, err.numeric_code());
+/// assert_eq!("queue was empty", err.ty().to_string());
+/// ```
+///
+/// [`metric_label`]: ErrorType::metric_label
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompactError {
+    /// The numeric code of the error.
+    pub code: u32,
+    /// The message of the error.
+    pub message: &'static str,
+    /// The location where the error occurred.
+    pub origin: Option<&'static ConstLocation>,
+}
+
+impl CompactError {
+    /// Creates a new [`CompactError`] without an origin.
+    pub const fn new(code: u32, message: &'static str) -> CompactError {
+        CompactError {
+            code,
+            message,
+            origin: None,
+        }
+    }
+
+    /// Sets the origin of the error. Commonly used with the [`flc!`] macro.
+    pub const fn at(mut self, origin: &'static ConstLocation) -> CompactError {
+        self.origin = Some(origin);
+        self
+    }
+
+    /// Converts the error into a full [`EzError`] of the type
+    /// [`ErrorType::Custom`] with the origin as its only frame.
+    pub fn into_ez_error(self) -> EzError {
+        let mut err = EzError::custom(self.code, "Compact".into(), self.message.into());
+        if let Some(origin) = self.origin {
+            err.add_frame(origin);
+        }
+        err
+    }
+}
+
+impl From<&EzError> for CompactError {
+    fn from(err: &EzError) -> Self {
+        #[cfg(not(feature = "no_stacktrace"))]
+        let origin = err.frames().first().copied();
+        #[cfg(feature = "no_stacktrace")]
+        let origin = None;
+
+        CompactError {
+            code: err.numeric_code(),
+            message: err.metric_label(),
+            origin,
+        }
+    }
+}
+
+impl From<CompactError> for EzError {
+    fn from(err: CompactError) -> Self {
+        err.into_ez_error()
+    }
+}
+
+#[cfg(all(test, not(feature = "panic_on_error")))]
+mod tests {
+    use super::*;
+
+    const ORIGIN: ConstLocation = ConstLocation::new("src/queue.rs", 8, 13);
+
+    #[test]
+    fn into_ez_error() {
+        let compact = CompactError::new(1001, "queue was empty").at(&ORIGIN);
+        let err = compact.into_ez_error();
+
+        assert_eq!(1001, err.numeric_code());
+        assert_eq!("Compact", err.ty().name());
+        assert_eq!("queue was empty", err.ty().to_string());
+        assert_eq!(&[&ORIGIN], err.frames());
+    }
+
+    #[test]
+    fn from_ez_error() {
+        let mut err = EzError::new(ErrorType::IndexOutOfBounds(4, 2));
+        err.add_frame(&ORIGIN);
+        err.add_frame(crate::flc!());
+
+        let compact = CompactError::from(&err);
+        assert_eq!(
+            CompactError::new(2, "index_out_of_bounds").at(&ORIGIN),
+            compact
+        );
+    }
+}
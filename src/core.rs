@@ -1,15 +1,103 @@
 //! Core code.
 
-/// A custom [`std::result::Result<T, E>`] with the [`EzError`] type. This is used for
+use crate::alloc_prelude::*;
+#[cfg(feature = "std")]
+use crate::render::HandleOptions;
+#[cfg(any(feature = "std", not(feature = "no_stacktrace")))]
+use alloc::collections::btree_map::Entry;
+#[cfg(any(feature = "std", not(feature = "no_stacktrace")))]
+use alloc::collections::BTreeMap;
+#[cfg(feature = "std")]
+use core::sync::atomic::AtomicBool;
+use core::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(feature = "std")]
+use std::sync::{Mutex, RwLock};
+
+/// A custom [`core::result::Result<T, E>`] with the [`EzError`] type. This is used for
 /// passing down errors.
-pub type Result<T> = std::result::Result<T, EzError>;
+pub type Result<T> = core::result::Result<T, EzError>;
 
 /// Throws an error and returns early.
 /// Shortcut for `Err(EzError::message("some error")).loc(flc!())?`
 #[macro_export]
 macro_rules! bail {
     ($($args:tt)*) => {
-        Err(EzError::message(&::std::format_args!($($args)*).to_string())).loc(flc!())?
+        Err(EzError::message(&$crate::__private::format!($($args)*))).loc(flc!())?
+    };
+}
+
+/// Throws an error and returns early if the condition is `false`.
+/// Shortcut for `if !cond { bail!("some error") }`
+///
+/// ```
+/// # use ez_err::prelude::*;
+/// fn check_port(port: u32) -> Result<()> {
+///     ensure!(port <= 65535, "port {} is too large", port);
+///
+///     Ok(())
+/// }
+///
+/// assert!(check_port(80).is_ok());
+/// assert!(check_port(70000).is_err());
+/// ```
+#[macro_export]
+macro_rules! ensure {
+    ($cond:expr, $($args:tt)*) => {
+        if !($cond) {
+            $crate::bail!($($args)*);
+        }
+    };
+}
+
+/// Throws an [`ErrorType::Custom`] error and returns early.
+/// Shortcut for `Err(EzError::custom(code, name, format!(...))).loc(flc!())?`
+///
+/// ```
+/// # use ez_err::prelude::*;
+/// fn load() -> Result<()> {
+///     bail_custom!(code = 42, name = "LoadError", "could not load {}", "config.toml");
+///
+///     Ok(())
+/// }
+///
+/// let err = load().err().unwrap();
+/// assert_eq!(Some(&42), match err.ty() {
+///     ErrorType::Custom { code, .. } => Some(code),
+///     _ => None,
+/// });
+/// ```
+#[macro_export]
+macro_rules! bail_custom {
+    (code = $code:expr, name = $name:expr, $($args:tt)*) => {
+        Err(EzError::custom(
+            $code,
+            $crate::__private::String::from($name),
+            $crate::__private::format!($($args)*),
+        ))
+        .loc(flc!())?
+    };
+}
+
+/// Adds the location of the invocation to the error and returns early.
+/// Shortcut for `expr.loc(flc!())?`
+///
+/// ```
+/// # use ez_err::prelude::*;
+/// # #[cfg(feature = "std")] {
+/// fn parse_port(port: &str) -> Result<u16> {
+///     let port = etry!(port.parse::<u16>());
+///
+///     Ok(port)
+/// }
+///
+/// assert_eq!(Ok(8080), parse_port("8080"));
+/// assert_eq!(1, parse_port("http").unwrap_err().frames().len());
+/// # }
+/// ```
+#[macro_export]
+macro_rules! etry {
+    ($expr:expr $(,)?) => {
+        $expr.loc(flc!())?
     };
 }
 
@@ -19,15 +107,116 @@ macro_rules! bail {
 macro_rules! flc {
     () => {{
         #[cfg(not(feature = "no_stacktrace"))]
-        const LOC: ConstLocation = ConstLocation::new(file!(), line!(), column!());
+        const LOC: ConstLocation = ConstLocation::new(file!(), line!(), column!())
+            .with_module(module_path!())
+            .with_function({
+                fn name() -> &'static str {
+                    fn f() {}
+                    $crate::core::function_name(f)
+                }
+                name
+            });
         #[cfg(feature = "no_stacktrace")]
         const LOC: ConstLocation = ConstLocation::new("", 0, 0);
         &LOC
     }};
 }
 
+/// Only keeps frames that belong to the calling crate from now on. Frames from
+/// dependencies are dropped when they are added, which keeps errors small and
+/// avoids leaking paths of dependencies. Expands to a call of
+/// [`set_local_root`] with the `CARGO_MANIFEST_DIR` of the calling crate.
+/// Only available with the `std` feature.
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! local_frames_only {
+    () => {
+        $crate::core::set_local_root(::core::option::Option::Some(::core::env!(
+            "CARGO_MANIFEST_DIR"
+        )))
+    };
+}
+
+#[cfg(feature = "std")]
+static LOCAL_ONLY: AtomicBool = AtomicBool::new(false);
+#[cfg(feature = "std")]
+static LOCAL_ROOT: RwLock<Option<&'static str>> = RwLock::new(None);
+
+/// Sets the root directory of the local crate. While a root is set,
+/// [`EzError::add_frame`] drops all frames that are not local. A frame is
+/// local if its file path is relative (cargo passes relative paths for the
+/// crates of the current workspace) or starts with `root`. Dependencies from
+/// a registry or git checkout have absolute paths outside of `root` and are
+/// dropped. Passing `None` keeps all frames again, which is the default.
+///
+/// Usually called through [`local_frames_only!`].
+#[cfg(feature = "std")]
+pub fn set_local_root(root: Option<&'static str>) {
+    *LOCAL_ROOT.write().unwrap_or_else(|e| e.into_inner()) = root;
+    LOCAL_ONLY.store(root.is_some(), Ordering::Release);
+}
+
+/// Returns whether a frame in the given file should be recorded.
+#[cfg(all(feature = "std", not(feature = "no_stacktrace")))]
+fn is_local(file: &str) -> bool {
+    if !LOCAL_ONLY.load(Ordering::Acquire) || !std::path::Path::new(file).is_absolute() {
+        return true;
+    }
+
+    match *LOCAL_ROOT.read().unwrap_or_else(|e| e.into_inner()) {
+        Some(root) => file.starts_with(root),
+        None => true,
+    }
+}
+
+#[cfg(feature = "std")]
+static NONE_MESSAGE: RwLock<Option<&'static str>> = RwLock::new(None);
+
+/// Sets the message that is used for all [`ErrorType::NoneOption`] errors,
+/// e.g. `"required value missing"`. Passing `None` restores the default
+/// `"Option was none"` text.
+///
+/// The message is looked up whenever the error is formatted, so it also
+/// applies to errors that were created before it was set.
+#[cfg(feature = "std")]
+pub fn set_none_message(msg: Option<&'static str>) {
+    *NONE_MESSAGE.write().unwrap_or_else(|e| e.into_inner()) = msg;
+}
+
+#[cfg(feature = "std")]
+static GLOBAL_HANDLER: RwLock<Option<fn(&EzError)>> = RwLock::new(None);
+
+/// Sets the handler that is called by [`Handle::handle`] and
+/// [`EzError::report`] for every error instead of printing it, e.g. to panic
+/// during development and to only count errors in production. Passing `None`
+/// restores the default output to the log or the console.
+///
+/// Errors are still added to the [`RingLog`] of the [`HandleOptions`].
+///
+/// The handler receives the full error, also if it was already reported
+/// with [`EzError::report`] and would only be printed as
+/// `(already reported)`. Use [`EzError::is_reported`] to skip such errors.
+///
+/// [`RingLog`]: crate::ring_log::RingLog
+#[cfg(feature = "std")]
+pub fn set_global_handler(handler: Option<fn(&EzError)>) {
+    *GLOBAL_HANDLER.write().unwrap_or_else(|e| e.into_inner()) = handler;
+}
+
+static MAX_FRAMES: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+/// Sets the maximum number of frames that an error records. Frames added
+/// after the limit is reached are only counted and the stacktrace ends with
+/// `... N frames truncated`, which keeps the memory of errors that are
+/// propagated through long chains or loops bounded. Passing `None` removes
+/// the limit, which is the default.
+pub fn set_max_frames(max: Option<usize>) {
+    MAX_FRAMES.store(max.unwrap_or(usize::MAX), Ordering::Relaxed);
+}
+
 /// Execute the provided function and catch any errors. This is
 /// useful for closures where no error type can be returned by default.
+#[cfg(feature = "std")]
 pub fn handle<F, R>(func: F) -> Option<R>
 where
     F: FnOnce() -> Result<R>,
@@ -35,34 +224,167 @@ where
     func().handle()
 }
 
+/// Executes the provided function and measures how long it ran. If it
+/// returns an error, a `failed after <duration>` context message is attached,
+/// which helps to tell fast validation failures from slow ones.
+#[cfg(feature = "std")]
+pub fn run_timed<F, R>(func: F) -> Result<R>
+where
+    F: FnOnce() -> Result<R>,
+{
+    let start = std::time::Instant::now();
+    func().map_err(|e| e.context(&format!("failed after {:?}", start.elapsed())))
+}
+
+/// The maximum number of bytes of an input snapshot stored by [`EzError::with_input`].
+pub const MAX_INPUT_LEN: usize = 256;
+
 /// Stores information about the error and is used for proper error
 /// output to the Unity console.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct EzError {
     inner: Box<EzErrorInner>,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct EzErrorInner {
     ty: ErrorType,
+    input: Option<String>,
+    context: Vec<String>,
+    causes: Vec<String>,
+    url: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    code: Option<u32>,
+    transient: bool,
+    retry_after: Option<core::time::Duration>,
+    reported: bool,
     #[cfg(not(feature = "no_stacktrace"))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(deserialize_with = "crate::serde_impl::deserialize_frames")
+    )]
     frames: Vec<&'static ConstLocation>,
+    #[cfg(not(feature = "no_stacktrace"))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(deserialize_with = "crate::serde_impl::deserialize_markers")
+    )]
+    markers: Vec<(usize, FrameMarker)>,
+    #[cfg(not(feature = "no_stacktrace"))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    truncated: usize,
+    #[cfg(all(feature = "timestamps", not(feature = "no_stacktrace")))]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    times: FrameTimes,
+    #[cfg(feature = "std_backtrace")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    backtrace: OsBacktrace,
+}
+
+/// The backtrace captured when the error was converted from a foreign error.
+/// Like timestamps, it is ignored when errors are compared.
+#[cfg(feature = "std_backtrace")]
+#[derive(Debug, Clone, Default)]
+struct OsBacktrace(Option<std::sync::Arc<std::backtrace::Backtrace>>);
+
+#[cfg(feature = "std_backtrace")]
+impl PartialEq for OsBacktrace {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+/// The times at which the frames were added. Timestamps are not part of the
+/// identity of an error and are ignored when errors are compared.
+#[cfg(all(feature = "timestamps", not(feature = "no_stacktrace")))]
+#[derive(Debug, Clone, Default)]
+struct FrameTimes(Vec<std::time::Instant>);
+
+#[cfg(all(feature = "timestamps", not(feature = "no_stacktrace")))]
+impl PartialEq for FrameTimes {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+/// Extra information attached to a position in the frame list. Markers are
+/// stored next to the frames together with the index of the frame they
+/// belong to and are kept sorted by that index.
+#[cfg(not(feature = "no_stacktrace"))]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub(crate) enum FrameMarker {
+    /// A labeled boundary that is printed before the frame at the index.
+    Boundary(&'static str),
+    /// A tag that is printed after the frame at the index.
+    Tag(u64),
+    /// The number of identical consecutive frames that were collapsed into
+    /// the frame at the index by [`EzError::dedup_consecutive`].
+    Repeat(usize),
+    /// A note that is printed after the frame at the index.
+    Note(&'static str),
 }
 
 impl EzError {
     /// Constructs a new `EzError` with the given error type.
+    ///
+    /// With the `panic_on_error` feature this panics instead in debug builds,
+    /// so the origin of an error can be found in the backtrace of the panic.
+    /// Release builds return the error as usual, so the feature can't turn
+    /// a handled error into a crash if it is left enabled by accident.
     pub fn new(ty: ErrorType) -> EzError {
-        #[cfg(not(feature = "no_stacktrace"))]
-        return EzError {
+        #[cfg(all(feature = "panic_on_error", debug_assertions))]
+        panic!(
+            "{} error created with `panic_on_error` enabled: {}",
+            ty.name(),
+            ty
+        );
+
+        #[allow(unreachable_code)]
+        let err = EzError::wrapper(ty);
+        #[cfg(feature = "std")]
+        let err = {
+            let mut err = err;
+            err.inner.context = crate::scope::current_scopes();
+            err
+        };
+        err
+    }
+
+    /// Constructs an error of the given type that wraps existing errors.
+    /// Unlike [`EzError::new`] it neither records the current error scopes,
+    /// which the wrapped errors already have, nor panics with the
+    /// `panic_on_error` feature.
+    fn wrapper(ty: ErrorType) -> EzError {
+        EzError {
             inner: Box::new(EzErrorInner {
                 ty,
+                input: None,
+                context: Vec::new(),
+                causes: Vec::new(),
+                url: None,
+                code: None,
+                transient: false,
+                retry_after: None,
+                reported: false,
+                #[cfg(not(feature = "no_stacktrace"))]
                 frames: Vec::new(),
+                #[cfg(not(feature = "no_stacktrace"))]
+                markers: Vec::new(),
+                #[cfg(not(feature = "no_stacktrace"))]
+                truncated: 0,
+                #[cfg(all(feature = "timestamps", not(feature = "no_stacktrace")))]
+                times: FrameTimes::default(),
+                #[cfg(feature = "std_backtrace")]
+                backtrace: OsBacktrace::default(),
             }),
-        };
-        #[cfg(feature = "no_stacktrace")]
-        return EzError {
-            inner: Box::new(EzErrorInner { ty }),
-        };
+        }
     }
 
     /// Constructs a new [`EzError`] with the type [`ErrorType::Message`]
@@ -81,220 +403,2016 @@ impl EzError {
         })
     }
 
-    /// Adds a new frame to the `EzError` and sets `file_name`
-    /// to `file` and `line_number` to `line`.
-    pub fn add_frame(&mut self, loc: &'static ConstLocation) {
-        self.inner.frames.push(loc);
+    /// Combines several errors into one [`ErrorType::Multiple`] error. Each
+    /// error keeps its own frames, which are printed indented below the
+    /// trace of the combined error.
+    pub fn aggregate(errors: Vec<EzError>) -> EzError {
+        EzError::new(ErrorType::Multiple(errors))
     }
 
-    /// Merges the other error into this by adding the frames of it to this.
-    pub fn with(mut self, other: EzError) -> Self {
-        self.inner.frames.extend_from_slice(&other.inner.frames);
-        self
+    /// Constructs a new [`EzError`] with the type [`ErrorType::Timeout`]. The
+    /// error is marked as [`transient`], since the operation may succeed if
+    /// it is tried again.
+    ///
+    /// [`transient`]: EzError::transient
+    pub fn timeout(dur: core::time::Duration) -> EzError {
+        EzError::new(ErrorType::Timeout(dur)).transient()
     }
 
-    /// Returns the type of the error.
-    pub fn ty(&self) -> &ErrorType {
-        &self.inner.ty
+    /// Constructs a new [`EzError`] with the type [`ErrorType::NotFound`] for
+    /// the given resource, e.g. `"user 42"`.
+    pub fn not_found(resource: impl Into<String>) -> EzError {
+        EzError::new(ErrorType::NotFound {
+            resource: resource.into(),
+        })
     }
 
-    /// Returns the stack frames of the error.
-    #[cfg(not(feature = "no_stacktrace"))]
-    pub fn frames(&self) -> &[&'static ConstLocation] {
-        &self.inner.frames
+    /// Returns an [`EzErrorBuilder`] to construct an error with several frames
+    /// at once, e.g. for tests or synthetic errors.
+    pub fn builder() -> EzErrorBuilder {
+        EzErrorBuilder::default()
     }
-}
 
-impl<E> From<E> for EzError
-where
-    E: std::fmt::Display,
-{
-    fn from(err: E) -> Self {
-        EzError::new(ErrorType::Internal(format!("{}", err)))
+    /// Constructs a new [`EzError`] with the type [`ErrorType::Panic`] from
+    /// the payload of a caught panic, e.g. the error returned by
+    /// `std::panic::catch_unwind`. The message is taken from `&str` and
+    /// `String` payloads, any other payload results in `"unknown panic"`.
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// fn run_plugin() -> Result<()> {
+    ///     std::panic::catch_unwind(|| panic!("plugin crashed"))
+    ///         .map_err(EzError::from_panic)
+    ///         .loc(flc!())?;
+    ///
+    ///     Ok(())
+    /// }
+    /// # let hook = std::panic::take_hook();
+    /// # std::panic::set_hook(Box::new(|_| {}));
+    /// # let res = run_plugin();
+    /// # std::panic::set_hook(hook);
+    /// assert_eq!(&ErrorType::Panic("plugin crashed".into()), res.unwrap_err().ty());
+    /// ```
+    pub fn from_panic(payload: Box<dyn core::any::Any + Send>) -> EzError {
+        let msg = match payload.downcast::<String>() {
+            Ok(msg) => *msg,
+            Err(payload) => match payload.downcast_ref::<&str>() {
+                Some(msg) => (*msg).to_owned(),
+                None => "unknown panic".to_owned(),
+            },
+        };
+        EzError::new(ErrorType::Panic(msg))
     }
-}
 
-/// The different error types that can occur.
-#[derive(Debug, PartialEq)]
-pub enum ErrorType {
-    /// Wraps an internal error that is not compatible with the
-    /// custom error types by default.
-    Internal(String),
+    /// Adds a new frame to the `EzError` and sets `file_name`
+    /// to `file` and `line_number` to `line`.
+    ///
+    /// Frames outside of the local crate are dropped if a root was set
+    /// with [`set_local_root`]. With the `single_frame` feature only the
+    /// first frame, i.e. the origin of the error, is kept. Frames beyond the
+    /// limit set with [`set_max_frames`] are only counted.
+    pub fn add_frame(&mut self, loc: &'static ConstLocation) {
+        #[cfg(not(feature = "no_stacktrace"))]
+        if self.admit_frame(loc) {
+            self.inner.frames.push(loc);
+            #[cfg(feature = "timestamps")]
+            self.inner.times.0.push(std::time::Instant::now());
+        }
+        #[cfg(feature = "no_stacktrace")]
+        let _ = loc;
+    }
 
-    /// An error that occured where an `Option` was `None`.
-    NoneOption,
+    /// Inserts a new frame before all other frames of the `EzError`, while
+    /// [`EzError::add_frame`] appends it after them. Frames are ordered from
+    /// the origin of the error to the outermost caller, so the prepended
+    /// frame becomes the new origin. This is useful to build a trace in that
+    /// order when merging stored errors. Tags, notes and boundaries stay with
+    /// the frames they belong to.
+    ///
+    /// The same filters as for [`EzError::add_frame`] apply.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// const A: ConstLocation = ConstLocation::new("src/a.rs", 1, 1);
+    /// const B: ConstLocation = ConstLocation::new("src/b.rs", 2, 1);
+    ///
+    /// let mut err = EzError::message("boom");
+    /// err.add_frame(&B);
+    /// err.prepend_frame(&A);
+    ///
+    /// # #[cfg(not(any(feature = "single_frame", feature = "timestamps")))]
+    /// assert_eq!("src/a.rs:1:1\nsrc/b.rs:2:1\n", err.trace_string());
+    /// ```
+    pub fn prepend_frame(&mut self, loc: &'static ConstLocation) {
+        #[cfg(not(feature = "no_stacktrace"))]
+        if self.admit_frame(loc) {
+            self.inner.frames.insert(0, loc);
+            for (idx, _) in &mut self.inner.markers {
+                *idx += 1;
+            }
+            // The elapsed times are relative to the first frame, so the new
+            // origin takes over its time instead of a later one.
+            #[cfg(feature = "timestamps")]
+            {
+                let times = &mut self.inner.times.0;
+                let first = times.first().copied();
+                times.insert(0, first.unwrap_or_else(std::time::Instant::now));
+            }
+        }
+        #[cfg(feature = "no_stacktrace")]
+        let _ = loc;
+    }
 
-    /// An error that occured when an array index was outside of the
-    /// valid range.
-    IndexOutOfBounds(usize, usize),
+    /// Returns whether a new frame at `loc` should be stored. Frames beyond
+    /// the limit set with [`set_max_frames`] are counted as truncated.
+    #[cfg(not(feature = "no_stacktrace"))]
+    fn admit_frame(&mut self, loc: &'static ConstLocation) -> bool {
+        #[cfg(feature = "single_frame")]
+        if !self.inner.frames.is_empty() {
+            return false;
+        }
+        #[cfg(feature = "std")]
+        if !is_local(loc.file) {
+            return false;
+        }
+        #[cfg(not(feature = "std"))]
+        let _ = loc;
+        if self.inner.frames.len() >= MAX_FRAMES.load(Ordering::Relaxed) {
+            self.inner.truncated += 1;
+            return false;
+        }
+        true
+    }
 
-    /// The range is larger than the array.
-    RangeOutOfBounds(usize, usize, usize),
+    /// Adds a frame from a [`std::panic::Location`], e.g. the one returned by
+    /// [`Location::caller`] inside of a `#[track_caller]` function.
+    ///
+    /// The location must be `'static`, which is the case for all locations
+    /// created by the compiler. Every distinct location is converted into a
+    /// [`ConstLocation`] once and kept for the rest of the program, so the
+    /// memory used is bounded by the number of call sites.
+    ///
+    /// [`Location::caller`]: std::panic::Location::caller
+    #[cfg(feature = "std")]
+    pub fn add_std_location(&mut self, loc: &'static std::panic::Location<'static>) {
+        self.add_frame(intern_location(loc.file(), loc.line(), loc.column(), ""));
+    }
 
-    /// The given range index is not valid (`end < start`).
-    InvalidRange,
+    /// Adds a new frame together with a tag, e.g. a loop index or a request
+    /// id, that tells otherwise identical frames apart. The frame is printed
+    /// as `file:line:column #tag`.
+    pub fn add_tagged_frame(&mut self, loc: &'static ConstLocation, tag: u64) {
+        #[cfg(not(feature = "no_stacktrace"))]
+        {
+            let idx = self.inner.frames.len();
+            self.add_frame(loc);
+            if self.inner.frames.len() > idx {
+                self.inner.markers.push((idx, FrameMarker::Tag(tag)));
+            }
+        }
+        #[cfg(feature = "no_stacktrace")]
+        let _ = (loc, tag);
+    }
 
-    /// A custom error with an attached message.
-    Message(String),
+    /// Adds a new frame together with a note that describes what was being
+    /// attempted at the location. The frame is printed as
+    /// `file:line:column - note`.
+    pub fn add_noted_frame(&mut self, loc: &'static ConstLocation, note: &'static str) {
+        #[cfg(not(feature = "no_stacktrace"))]
+        {
+            let idx = self.inner.frames.len();
+            self.add_frame(loc);
+            if self.inner.frames.len() > idx {
+                self.inner.markers.push((idx, FrameMarker::Note(note)));
+            }
+        }
+        #[cfg(feature = "no_stacktrace")]
+        let _ = (loc, note);
+    }
 
-    /// No error specified.
-    Custom {
-        /// A custom message code used for storing custom information.
-        code: u32,
-        /// The name of the error.
-        name: String,
-        /// The message of the error.
-        message: String,
-    },
-}
+    /// Adds a labeled boundary after the current frames. Boundaries are
+    /// printed as `--- label ---` between the surrounding frames and can be
+    /// used to separate the frames of different subsystems.
+    pub fn add_boundary(&mut self, label: &'static str) {
+        #[cfg(not(feature = "no_stacktrace"))]
+        self.inner
+            .markers
+            .push((self.inner.frames.len(), FrameMarker::Boundary(label)));
+        #[cfg(feature = "no_stacktrace")]
+        let _ = label;
+    }
 
-impl ErrorType {
-    /// Formats the error type into a String for console output.
-    pub fn format(self) -> String {
-        match self {
-            ErrorType::Internal(msg) => msg,
-            ErrorType::NoneOption => format!("Option was none"),
-            ErrorType::IndexOutOfBounds(idx, len) => {
-                format!("Index {} was outside of the range 0..{}", idx, len)
+    /// Merges the other error into this one without losing information:
+    ///
+    /// - If both errors have the same type, it is kept and the frames (and
+    ///   markers) of `other` are added after the frames of this error, so
+    ///   both frame sequences are kept in order. Frames beyond the limit set
+    ///   with [`set_max_frames`] are counted as truncated.
+    /// - Otherwise the type becomes an [`ErrorType::Multiple`] that contains
+    ///   both errors unchanged, each with its own frames. If this error
+    ///   already is an [`ErrorType::Multiple`], `other` (or the parts of it)
+    ///   is appended instead.
+    /// - The context messages and causes of `other` that this error does not
+    ///   have yet are appended.
+    /// - The input, url and code of `other` are only used if this error has
+    ///   none. The error is transient if either error is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// let err = EzError::new(ErrorType::IndexOutOfBounds(3, 2))
+    ///     .with(EzError::message("while loading"));
+    ///
+    /// let types: Vec<&ErrorType> = err.parts_iter().map(EzError::ty).collect();
+    /// assert_eq!(
+    ///     vec![&ErrorType::IndexOutOfBounds(3, 2), &ErrorType::Message("while loading".into())],
+    ///     types
+    /// );
+    /// ```
+    pub fn with(mut self, other: EzError) -> Self {
+        if self.inner.ty == other.inner.ty {
+            self.merge_info(&other);
+            self.append_frames(*other.inner);
+            return self;
+        }
+
+        if !self.is_multiple() {
+            let part =
+                core::mem::replace(&mut self, EzError::wrapper(ErrorType::Multiple(Vec::new())));
+            self.merge_info(&part);
+            self.inner.ty = ErrorType::Multiple(vec![part]);
+        }
+        self.merge_info(&other);
+        let others = if other.is_multiple() {
+            let mut other = *other.inner;
+            let others = match &mut other.ty {
+                ErrorType::Multiple(others) => core::mem::take(others),
+                _ => Vec::new(),
+            };
+            self.append_frames(other);
+            others
+        } else {
+            vec![other]
+        };
+        if let ErrorType::Multiple(parts) = &mut self.inner.ty {
+            parts.extend(others);
+        }
+        self
+    }
+
+    /// Merges the context messages, causes and other information of `other`
+    /// into this error as described for [`EzError::with`].
+    fn merge_info(&mut self, other: &EzError) {
+        let other = &other.inner;
+        for context in &other.context {
+            if !self.inner.context.contains(context) {
+                self.inner.context.push(context.clone());
             }
-            ErrorType::RangeOutOfBounds(start, end, len) => {
-                format!(
-                    "Range {}..{} was larger than the array range 0..{}",
-                    start, end, len
-                )
+        }
+        for cause in &other.causes {
+            if !self.inner.causes.contains(cause) {
+                self.inner.causes.push(cause.clone());
             }
-            ErrorType::InvalidRange => {
-                "The provided range was invalid (end < start or X..=usize::MAX)".into()
+        }
+        if self.inner.input.is_none() {
+            self.inner.input = other.input.clone();
+        }
+        if self.inner.url.is_none() {
+            self.inner.url = other.url.clone();
+        }
+        if self.inner.code.is_none() {
+            self.inner.code = other.code;
+        }
+        self.inner.transient |= other.transient;
+    }
+
+    /// Adds the frames and markers of `other` after the frames of this error.
+    /// The frames pass the same filters as for [`EzError::add_frame`]; the
+    /// markers of a dropped frame are dropped with it, except boundaries.
+    fn append_frames(&mut self, other: EzErrorInner) {
+        #[cfg(not(feature = "no_stacktrace"))]
+        {
+            let mut markers = other.markers.into_iter().peekable();
+            #[cfg(feature = "timestamps")]
+            let mut times = other.times.0.into_iter();
+            for (idx, frame) in other.frames.into_iter().enumerate() {
+                #[cfg(feature = "timestamps")]
+                let time = times.next();
+                let admitted = self.admit_frame(frame);
+                let new_idx = self.inner.frames.len();
+                while let Some((_, marker)) = markers.next_if(|(i, _)| *i <= idx) {
+                    if admitted || matches!(marker, FrameMarker::Boundary(_)) {
+                        self.inner.markers.push((new_idx, marker));
+                    }
+                }
+                if admitted {
+                    self.inner.frames.push(frame);
+                    #[cfg(feature = "timestamps")]
+                    self.inner.times.0.extend(time);
+                }
             }
-            ErrorType::Message(msg) => msg,
-            ErrorType::Custom { message, .. } => message,
+            let end = self.inner.frames.len();
+            self.inner
+                .markers
+                .extend(markers.map(|(_, marker)| (end, marker)));
+            self.inner.truncated += other.truncated;
         }
+        #[cfg(feature = "no_stacktrace")]
+        let _ = other;
     }
 
-    /// Returns the name of the `ErrorType` that should be included
-    /// in the stacktrace.
-    pub fn name(&self) -> &str {
-        match self {
-            ErrorType::Internal(_) => "WrappedInternal",
-            ErrorType::NoneOption => "NoneOption",
-            ErrorType::IndexOutOfBounds(_, _) => "IndexOutOfBounds",
-            ErrorType::RangeOutOfBounds(_, _, _) => "RangeOutOfBounds",
-            ErrorType::InvalidRange => "InvalidRange",
-            ErrorType::Message(_) => "Message",
-            ErrorType::Custom { name, .. } => &name,
+    /// Attaches a snapshot of the input that produced this error, stored as
+    /// its [`Debug`] representation. The snapshot is truncated to
+    /// [`MAX_INPUT_LEN`] bytes and printed as `input: ...` by [`Handle::handle`].
+    pub fn with_input<T: core::fmt::Debug>(mut self, input: &T) -> Self {
+        let mut repr = format!("{:?}", input);
+        if repr.len() > MAX_INPUT_LEN {
+            let mut end = MAX_INPUT_LEN;
+            while !repr.is_char_boundary(end) {
+                end -= 1;
+            }
+            repr.truncate(end);
+            repr.push_str("...");
         }
+        self.inner.input = Some(repr);
+        self
     }
-}
 
-/// Information about the location in a source file in a constant context.
-#[derive(Debug, PartialEq)]
-pub struct ConstLocation {
-    /// The file of the location.
-    pub file: &'static str,
-    /// The line of the location.
-    pub line: u32,
-    /// The column of the location.
-    pub column: u32,
-}
+    /// Returns the input snapshot attached with [`EzError::with_input`].
+    pub fn input(&self) -> Option<&str> {
+        self.inner.input.as_deref()
+    }
 
-impl ConstLocation {
-    /// Creates a new [`ConstLocation`] using the given file and line.
-    pub const fn new(file: &'static str, line: u32, column: u32) -> ConstLocation {
-        ConstLocation { file, line, column }
+    /// Attaches a human-readable context message to the error. Context
+    /// messages are printed in the order they were added, after the messages
+    /// of the [`ErrorScope`]s the error was created in.
+    ///
+    /// [`ErrorScope`]: crate::scope::ErrorScope
+    pub fn context(mut self, msg: &str) -> EzError {
+        self.inner.context.push(msg.to_owned());
+        self
     }
-}
 
-/// Extension for `Result<T>` to allow for custom error handling.
-pub trait LocData<T> {
-    /// The return type of `add_info`. This can be used to convert
-    /// between different error types.
-    type Result;
+    /// Returns the context messages of the error.
+    pub fn contexts(&self) -> &[String] {
+        &self.inner.context
+    }
 
-    /// Adds a new frame info to the [`Result<T>`]. This only happens
-    /// when the [`Result<T>`] is [`Err(T)`]. Commonly used with the [`flc!`] macro.
-    fn loc(self, flc: &'static ConstLocation) -> Self::Result;
-}
+    /// Records the [`Display`] output of an underlying error as a cause,
+    /// without nesting another [`EzError`]. Causes are printed as
+    /// `Caused by: ...` lines in the order they were added.
+    ///
+    /// [`Display`]: core::fmt::Display
+    pub fn because(mut self, cause: impl core::fmt::Display) -> Self {
+        self.inner.causes.push(cause.to_string());
+        self
+    }
 
-/// Extension for `Result<T>` to allow for custom error handling.
-pub trait Handle<T> {
-    /// Handles the result. If it contains an error a backtrace is
-    /// created and the error is printed to the console.
-    fn handle(self) -> Option<T>;
+    /// Returns the causes recorded with [`EzError::because`].
+    pub fn causes(&self) -> &[String] {
+        &self.inner.causes
+    }
 
-    /// Handles the result or panics if it is [`Err`]. If it contains
-    /// an error a backtrace is created and the error is printed to the console.
-    fn handle_or_panic(self) -> T;
-}
+    /// Attaches a URL to documentation or a runbook that helps with resolving
+    /// the error. The URL is printed as a `see: <url>` line.
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        self.inner.url = Some(url.into());
+        self
+    }
 
-impl<T> LocData<T> for Result<T> {
-    type Result = Result<T>;
+    /// Returns the URL attached with [`EzError::with_url`].
+    pub fn url(&self) -> Option<&str> {
+        self.inner.url.as_deref()
+    }
 
-    #[inline(always)]
-    fn loc(mut self, loc: &'static ConstLocation) -> Self::Result {
-        if let Err(err) = &mut self {
-            err.add_frame(loc);
-        }
+    /// Attaches a numeric code to the error that is returned by
+    /// [`EzError::code`], e.g. for reporting APIs that need a stable code for
+    /// every error. This works for all error types and overrides the code of
+    /// an [`ErrorType::Custom`] error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// let err = EzError::new(ErrorType::IndexOutOfBounds(3, 2)).with_code(4001);
+    /// assert_eq!(Some(4001), err.code());
+    /// ```
+    pub fn with_code(mut self, code: u32) -> Self {
+        self.inner.code = Some(code);
+        self
+    }
 
+    /// Marks the error as transient, i.e. the operation that failed may
+    /// succeed if it is tried again (e.g. a timeout or a dropped connection).
+    /// Retry helpers like [`retry_backoff`] stop early on errors that are not
+    /// transient.
+    ///
+    /// [`retry_backoff`]: crate::retry::retry_backoff
+    pub fn transient(mut self) -> Self {
+        self.inner.transient = true;
         self
     }
-}
 
-impl<T> Handle<T> for Result<T> {
-    fn handle(self) -> Option<T> {
-        fn inner(e: EzError) {
-            let e = e.inner;
-
-            #[cfg(not(feature = "no_stacktrace"))]
-            let trace = {
-                let mut s = String::with_capacity(1024);
-                s.push_str("Stacktrace:\n");
-                for frame in e.frames {
-                    s.push_str(frame.file);
-                    s.push(':');
-                    s.push_str(&frame.line.to_string());
-                    s.push(':');
-                    s.push_str(&frame.column.to_string());
-                    s.push('\n');
-                }
-                s
-            };
-            #[cfg(feature = "no_stacktrace")]
-            let trace = "";
+    /// Returns `true` if the error was marked with [`EzError::transient`].
+    pub fn is_transient(&self) -> bool {
+        self.inner.transient
+    }
 
-            let name = e.ty.name().to_owned();
-            let message = e.ty.format();
+    /// Attaches a suggested wait before the operation is tried again, e.g. the
+    /// value of a `Retry-After` header of a rate limited request. This also
+    /// marks the error as [`transient`]. [`retry_backoff`] waits for this
+    /// duration instead of its own backoff.
+    ///
+    /// [`transient`]: EzError::transient
+    /// [`retry_backoff`]: crate::retry::retry_backoff
+    pub fn retry_after(mut self, dur: core::time::Duration) -> Self {
+        self.inner.retry_after = Some(dur);
+        self.inner.transient = true;
+        self
+    }
 
-            #[cfg(feature = "log")]
-            log::error!("Error {}: {}\n\n{}", name, message, trace);
-            #[cfg(not(feature = "log"))]
-            println!("Error {}: {}\n\n{}", name, message, trace);
-        }
+    /// Returns the wait suggested with [`EzError::retry_after`].
+    pub fn retry_after_duration(&self) -> Option<core::time::Duration> {
+        self.inner.retry_after
+    }
 
-        match self {
-            Ok(v) => Some(v),
-            Err(e) => {
-                inner(e);
-                None
-            }
-        }
+    /// Prints the error like [`Handle::handle`] without consuming it and marks
+    /// it as reported. Reporting an error that was already reported (e.g.
+    /// by a middleware before it was re-thrown) only prints a compact
+    /// `(already reported)` line instead of the full error.
+    #[cfg(feature = "std")]
+    pub fn report(&mut self) {
+        self.report_with_options(&HandleOptions::default());
     }
-    fn handle_or_panic(self) -> T {
-        match self.handle() {
-            Some(v) => v,
-            None => panic!(),
-        }
+
+    /// Reports the error like [`EzError::report`] using the given [`HandleOptions`].
+    #[cfg(feature = "std")]
+    pub fn report_with_options(&mut self, opts: &HandleOptions) {
+        self.emit(opts);
+        self.inner.reported = true;
     }
-}
 
-impl<T, E> LocData<T> for std::result::Result<T, E>
+    /// Returns whether the error was already reported by [`EzError::report`].
+    pub fn is_reported(&self) -> bool {
+        self.inner.reported
+    }
+
+    /// Passes the error to the global handler or writes its output to the
+    /// log or stderr, and writes it to the [`RingLog`] of the options,
+    /// if any.
+    ///
+    /// [`RingLog`]: crate::ring_log::RingLog
+    #[cfg(feature = "std")]
+    fn emit(&self, opts: &HandleOptions) {
+        self.emit_output(self.output(opts), opts, || self.output(&opts.for_console()));
+    }
+
+    /// Emits the given output of the error like [`EzError::emit`]. The output
+    /// for stderr is only created by `console` if the error is printed there,
+    /// so that console-only options don't leak into the other sinks.
+    #[cfg(feature = "std")]
+    fn emit_output<F: FnOnce() -> String>(&self, output: String, opts: &HandleOptions, console: F) {
+        if let Some(ring_log) = &opts.ring_log {
+            ring_log.push(output.clone());
+        }
+
+        let handler = *GLOBAL_HANDLER.read().unwrap_or_else(|e| e.into_inner());
+        if let Some(handler) = handler {
+            handler(self);
+            return;
+        }
+
+        // An error that was already reported is only mentioned, without the
+        // fields that repeat the message and trace.
+        #[cfg(feature = "tracing")]
+        if self.is_reported() {
+            tracing::error!(error.name = self.ty().name(), "{}", output);
+        } else {
+            tracing::error!(
+                error.name = self.ty().name(),
+                error.message = %self.ty(),
+                error.trace = %self.trace_string(),
+                "{}",
+                output.lines().next().unwrap_or_default()
+            );
+        }
+        #[cfg(all(feature = "log", not(feature = "tracing")))]
+        log::error!("{}", output);
+        #[cfg(any(feature = "log", feature = "tracing"))]
+        let _ = console;
+        #[cfg(not(any(feature = "log", feature = "tracing")))]
+        eprintln!("{}", console());
+    }
+
+    /// Returns the text that is emitted when the error is reported.
+    #[cfg(feature = "std")]
+    fn output(&self, opts: &HandleOptions) -> String {
+        if self.inner.reported {
+            format!("Error {} (already reported)", self.inner.ty.name())
+        } else {
+            self.render_with(opts)
+        }
+    }
+
+    /// Splits an [`ErrorType::Multiple`] error into the errors it contains.
+    /// Any other error is returned as the only element.
+    pub fn into_parts_vec(mut self) -> Vec<EzError> {
+        match &mut self.inner.ty {
+            ErrorType::Multiple(errors) => core::mem::take(errors),
+            _ => vec![self],
+        }
+    }
+
+    /// Takes the error apart into its type, its frames and its context
+    /// messages, e.g. to convert it into the error type of a public API. The
+    /// frames are empty if the `no_stacktrace` feature is enabled. All other
+    /// information, e.g. the markers of the frames, is dropped.
+    ///
+    /// This method is not called `into_parts`, because the parts of an error
+    /// are the errors of an [`ErrorType::Multiple`], which are returned by
+    /// [`EzError::into_parts_vec`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// let res: Result<()> = Err(EzError::new(ErrorType::NoneOption)).loc(flc!());
+    /// let (ty, frames, contexts) = res.unwrap_err().into_components();
+    ///
+    /// assert_eq!(ErrorType::NoneOption, ty);
+    /// assert_eq!(1, frames.len());
+    /// assert!(contexts.is_empty());
+    /// ```
+    pub fn into_components(self) -> (ErrorType, Vec<&'static ConstLocation>, Vec<String>) {
+        let inner = *self.inner;
+        #[cfg(not(feature = "no_stacktrace"))]
+        let frames = inner.frames;
+        #[cfg(feature = "no_stacktrace")]
+        let frames = Vec::new();
+        (inner.ty, frames, inner.context)
+    }
+
+    /// Iterates over the errors of an [`ErrorType::Multiple`] error, or over
+    /// the error itself for any other error type.
+    pub fn parts_iter(&self) -> impl Iterator<Item = &EzError> {
+        match &self.inner.ty {
+            ErrorType::Multiple(errors) => errors.iter(),
+            _ => core::slice::from_ref(self).iter(),
+        }
+    }
+
+    /// Returns the type of the error.
+    pub fn ty(&self) -> &ErrorType {
+        &self.inner.ty
+    }
+
+    /// Returns a low-cardinality label for the error that is safe to use
+    /// in metrics. See [`ErrorType::metric_label`] for the mapping.
+    pub fn metric_label(&self) -> &'static str {
+        self.inner.ty.metric_label()
+    }
+
+    /// Returns the HTTP status code that best describes the error. See
+    /// [`ErrorType::http_status`] for the mapping.
+    pub fn http_status(&self) -> u16 {
+        self.inner.ty.http_status()
+    }
+
+    /// Returns the code attached with [`EzError::with_code`]. Without one,
+    /// returns the code of an [`ErrorType::Custom`] error and `None` for all
+    /// other error types.
+    pub fn code(&self) -> Option<u32> {
+        self.inner.code.or_else(|| self.inner.ty.code())
+    }
+
+    /// Returns a small, stable integer that identifies the kind of the error.
+    /// See [`ErrorType::numeric_code`] for the mapping.
+    pub fn numeric_code(&self) -> u32 {
+        self.inner.ty.numeric_code()
+    }
+
+    /// Returns whether the error is an [`ErrorType::Internal`].
+    pub fn is_internal(&self) -> bool {
+        matches!(self.inner.ty, ErrorType::Internal(_))
+    }
+
+    /// Returns whether the error is an [`ErrorType::NoneOption`].
+    pub fn is_none_option(&self) -> bool {
+        matches!(self.inner.ty, ErrorType::NoneOption)
+    }
+
+    /// Returns whether the error is an [`ErrorType::IndexOutOfBounds`].
+    pub fn is_index_oob(&self) -> bool {
+        matches!(self.inner.ty, ErrorType::IndexOutOfBounds(_, _))
+    }
+
+    /// Returns whether the error is an [`ErrorType::RangeOutOfBounds`].
+    pub fn is_range_oob(&self) -> bool {
+        matches!(self.inner.ty, ErrorType::RangeOutOfBounds(_, _, _))
+    }
+
+    /// Returns whether the error is an [`ErrorType::InvalidRange`].
+    pub fn is_invalid_range(&self) -> bool {
+        matches!(self.inner.ty, ErrorType::InvalidRange)
+    }
+
+    /// Returns whether the error is an [`ErrorType::EmptySlice`].
+    pub fn is_empty_slice(&self) -> bool {
+        matches!(self.inner.ty, ErrorType::EmptySlice)
+    }
+
+    /// Returns whether the error is an [`ErrorType::ShapeMismatch`].
+    pub fn is_shape_mismatch(&self) -> bool {
+        matches!(self.inner.ty, ErrorType::ShapeMismatch(_, _))
+    }
+
+    /// Returns whether the error is an [`ErrorType::DimensionOutOfBounds`].
+    pub fn is_dimension_oob(&self) -> bool {
+        matches!(self.inner.ty, ErrorType::DimensionOutOfBounds(_, _, _))
+    }
+
+    /// Returns whether the error is an [`ErrorType::Io`].
+    #[cfg(feature = "std")]
+    pub fn is_io(&self) -> bool {
+        matches!(self.inner.ty, ErrorType::Io(_, _))
+    }
+
+    /// Returns whether the error is an [`ErrorType::KeyNotFound`].
+    pub fn is_key_not_found(&self) -> bool {
+        matches!(self.inner.ty, ErrorType::KeyNotFound(_))
+    }
+
+    /// Returns whether the error is an [`ErrorType::Panic`].
+    pub fn is_panic(&self) -> bool {
+        matches!(self.inner.ty, ErrorType::Panic(_))
+    }
+
+    /// Returns whether the error is an [`ErrorType::Timeout`].
+    pub fn is_timeout(&self) -> bool {
+        matches!(self.inner.ty, ErrorType::Timeout(_))
+    }
+
+    /// Returns whether the error is an [`ErrorType::Parse`].
+    pub fn is_parse(&self) -> bool {
+        matches!(self.inner.ty, ErrorType::Parse { .. })
+    }
+
+    /// Returns whether the error is an [`ErrorType::Conversion`].
+    pub fn is_conversion(&self) -> bool {
+        matches!(self.inner.ty, ErrorType::Conversion { .. })
+    }
+
+    /// Returns whether the error is an [`ErrorType::DivByZero`].
+    pub fn is_div_by_zero(&self) -> bool {
+        matches!(self.inner.ty, ErrorType::DivByZero)
+    }
+
+    /// Returns whether the error is an [`ErrorType::ArithmeticOverflow`].
+    pub fn is_arithmetic_overflow(&self) -> bool {
+        matches!(self.inner.ty, ErrorType::ArithmeticOverflow)
+    }
+
+    /// Returns whether the error is an [`ErrorType::NotFound`].
+    pub fn is_not_found(&self) -> bool {
+        matches!(self.inner.ty, ErrorType::NotFound { .. })
+    }
+
+    /// Returns whether the error is an [`ErrorType::Multiple`].
+    pub fn is_multiple(&self) -> bool {
+        matches!(self.inner.ty, ErrorType::Multiple(_))
+    }
+
+    /// Returns whether the error is an [`ErrorType::Message`].
+    pub fn is_message(&self) -> bool {
+        matches!(self.inner.ty, ErrorType::Message(_))
+    }
+
+    /// Returns whether the error is an [`ErrorType::Custom`].
+    pub fn is_custom(&self) -> bool {
+        matches!(self.inner.ty, ErrorType::Custom { .. })
+    }
+
+    /// Returns whether the name of the error type (see [`ErrorType::name`])
+    /// is `name`, e.g. `"NoneOption"` or the name of an [`ErrorType::Custom`].
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// let err = EzError::custom(404, "NotFound".to_owned(), "no such user".to_owned());
+    /// assert!(err.matches_name("NotFound"));
+    /// assert!(err.is_custom());
+    /// assert!(!err.is_message());
+    /// ```
+    pub fn matches_name(&self, name: &str) -> bool {
+        self.inner.ty.name() == name
+    }
+
+    /// Returns `true` if the error is one of the built-in error types and
+    /// `false` for domain errors created by the user. See [`ErrorType::is_builtin`].
+    pub fn is_builtin(&self) -> bool {
+        self.inner.ty.is_builtin()
+    }
+
+    /// Returns the stack frames of the error.
+    #[cfg(not(feature = "no_stacktrace"))]
+    pub fn frames(&self) -> &[&'static ConstLocation] {
+        &self.inner.frames
+    }
+
+    /// Returns the location where the error was first recorded, i.e. the
+    /// first frame, or `None` if the error has no frames or the
+    /// `no_stacktrace` feature is enabled.
+    pub fn root_location(&self) -> Option<&'static ConstLocation> {
+        #[cfg(not(feature = "no_stacktrace"))]
+        {
+            self.inner.frames.first().copied()
+        }
+        #[cfg(feature = "no_stacktrace")]
+        None
+    }
+
+    /// Returns the location the error was last propagated through, i.e. the
+    /// last frame, or `None` if the error has no frames or the
+    /// `no_stacktrace` feature is enabled.
+    pub fn current_location(&self) -> Option<&'static ConstLocation> {
+        #[cfg(not(feature = "no_stacktrace"))]
+        {
+            self.inner.frames.last().copied()
+        }
+        #[cfg(feature = "no_stacktrace")]
+        None
+    }
+
+    /// Returns the number of frames that were not recorded because the limit
+    /// set with [`set_max_frames`] was reached.
+    #[cfg(not(feature = "no_stacktrace"))]
+    pub fn truncated_frames(&self) -> usize {
+        self.inner.truncated
+    }
+
+    /// Returns `true` if both errors describe the same incident: they have the
+    /// same [`ErrorType::name`] and their origin frames (the first frame of
+    /// each error) have the same file and line. The column, the messages and
+    /// all other frames are ignored. Two errors without frames have the same
+    /// root if their names are equal.
+    ///
+    /// This is coarser than `==`, which compares the complete errors, and is
+    /// intended for deduplicating alerts.
+    ///
+    /// With the `no_stacktrace` feature only the names are compared.
+    pub fn same_root(&self, other: &EzError) -> bool {
+        if self.ty().name() != other.ty().name() {
+            return false;
+        }
+
+        #[cfg(not(feature = "no_stacktrace"))]
+        match (self.frames().first(), other.frames().first()) {
+            (Some(a), Some(b)) => a.file == b.file && a.line == b.line,
+            (None, None) => true,
+            _ => false,
+        }
+        #[cfg(feature = "no_stacktrace")]
+        true
+    }
+
+    /// Returns `true` if both errors have the same [`ErrorType`], including
+    /// its values. Unlike `==`, which compares the complete errors, the
+    /// frames, context messages and all other metadata are ignored, so errors
+    /// of the same kind that were created at different locations are equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// let a: Result<()> = Err(EzError::new(ErrorType::NoneOption)).loc(flc!());
+    /// let b: Result<()> = Err(EzError::new(ErrorType::NoneOption)).loc(flc!());
+    /// assert!(a.unwrap_err().eq_kind(&b.unwrap_err()));
+    /// ```
+    pub fn eq_kind(&self, other: &EzError) -> bool {
+        self.ty() == other.ty()
+    }
+
+    /// Collapses runs of identical consecutive frames, e.g. from an error that
+    /// propagated through a recursive function, into a single frame. The
+    /// frame is printed with the number of collapsed frames as
+    /// `file:line:column (x42)`. Frames are only merged if their file, line
+    /// and column are the same and no tag, note or boundary lies between
+    /// them.
+    pub fn dedup_consecutive(&mut self) {
+        #[cfg(not(feature = "no_stacktrace"))]
+        {
+            let old_frames = core::mem::take(&mut self.inner.frames);
+            #[cfg(feature = "timestamps")]
+            let old_times = core::mem::take(&mut self.inner.times.0);
+            let mut old_markers = core::mem::take(&mut self.inner.markers)
+                .into_iter()
+                .peekable();
+            let mut frames: Vec<&'static ConstLocation> = Vec::with_capacity(old_frames.len());
+            let mut markers = Vec::new();
+            let mut repeat = 0;
+            let mut mergeable = false;
+            for (idx, frame) in old_frames.into_iter().enumerate() {
+                let mut count = 1;
+                let mut own = Vec::new();
+                while let Some((_, marker)) = old_markers.next_if(|(i, _)| *i == idx) {
+                    match marker {
+                        FrameMarker::Repeat(n) => count = n,
+                        marker => own.push(marker),
+                    }
+                }
+
+                let same = frames.last().is_some_and(|last| {
+                    last.file == frame.file
+                        && last.line == frame.line
+                        && last.column == frame.column
+                });
+                if mergeable && same && own.is_empty() {
+                    repeat += count;
+                    continue;
+                }
+
+                if repeat > 1 {
+                    markers.push((frames.len() - 1, FrameMarker::Repeat(repeat)));
+                }
+                mergeable = !own
+                    .iter()
+                    .any(|m| matches!(m, FrameMarker::Tag(_) | FrameMarker::Note(_)));
+                markers.extend(own.into_iter().map(|m| (frames.len(), m)));
+                #[cfg(feature = "timestamps")]
+                if let Some(time) = old_times.get(idx) {
+                    self.inner.times.0.push(*time);
+                }
+                frames.push(frame);
+                repeat = count;
+            }
+            if repeat > 1 {
+                markers.push((frames.len() - 1, FrameMarker::Repeat(repeat)));
+            }
+            markers.extend(old_markers.map(|(_, m)| (frames.len(), m)));
+
+            self.inner.frames = frames;
+            self.inner.markers = markers;
+        }
+    }
+
+    /// Returns the times at which the frames of the error were added, one
+    /// for every frame. Errors that were deserialized have no timestamps.
+    #[cfg(all(feature = "timestamps", not(feature = "no_stacktrace")))]
+    pub fn frame_times(&self) -> &[std::time::Instant] {
+        &self.inner.times.0
+    }
+
+    /// Returns the backtrace that was captured when the error was converted
+    /// from a foreign error. Only errors created by the conversion from
+    /// [`std::error::Error`] have a backtrace.
+    #[cfg(feature = "std_backtrace")]
+    pub fn backtrace(&self) -> Option<&std::backtrace::Backtrace> {
+        self.inner.backtrace.0.as_deref()
+    }
+
+    /// Returns the distinct stack frames of the error in the order of their
+    /// first occurrence, together with the number of times each one occurs.
+    ///
+    /// This is useful for errors that were propagated through the same
+    /// location many times, e.g. inside of a loop or a recursive function.
+    /// Frames are the same if their file, line and column are the same.
+    #[cfg(not(feature = "no_stacktrace"))]
+    pub fn unique_frames(&self) -> Vec<(&'static ConstLocation, usize)> {
+        let mut unique: Vec<(&'static ConstLocation, usize)> = Vec::new();
+        let mut positions: BTreeMap<_, usize> = BTreeMap::new();
+        let mut markers = self.inner.markers.iter().peekable();
+        for (idx, &frame) in self.inner.frames.iter().enumerate() {
+            // Collapsed repetitions still count separately.
+            let mut n = 1;
+            while let Some((_, marker)) = markers.next_if(|(i, _)| *i == idx) {
+                if let FrameMarker::Repeat(repeat) = marker {
+                    n = *repeat;
+                }
+            }
+            match positions.entry((frame.file, frame.line, frame.column)) {
+                Entry::Occupied(pos) => unique[*pos.get()].1 += n,
+                Entry::Vacant(pos) => {
+                    pos.insert(unique.len());
+                    unique.push((frame, n));
+                }
+            }
+        }
+        unique
+    }
+
+    /// Returns the markers attached to the frames of the error.
+    #[cfg(not(feature = "no_stacktrace"))]
+    pub(crate) fn markers(&self) -> &[(usize, FrameMarker)] {
+        &self.inner.markers
+    }
+}
+
+/// Builds an [`EzError`] from a type and a list of frames. Created with
+/// [`EzError::builder`].
+///
+/// # Examples
+///
+/// ```
+/// # use ez_err::prelude::*;
+/// const OUTER: ConstLocation = ConstLocation::new("src/main.rs", 7, 5);
+///
+/// let err = EzError::builder()
+///     .message("disk full")
+///     .frame(flc!())
+///     .frame(&OUTER)
+///     .build();
+/// assert_eq!(&ErrorType::Message("disk full".into()), err.ty());
+/// # #[cfg(not(feature = "single_frame"))]
+/// assert_eq!(2, err.frames().len());
+/// ```
+#[derive(Debug, Clone)]
+pub struct EzErrorBuilder {
+    ty: ErrorType,
+    frames: Vec<&'static ConstLocation>,
+}
+
+impl Default for EzErrorBuilder {
+    fn default() -> Self {
+        EzErrorBuilder {
+            ty: ErrorType::Message(String::new()),
+            frames: Vec::new(),
+        }
+    }
+}
+
+impl EzErrorBuilder {
+    /// Sets the type of the error. Defaults to an empty [`ErrorType::Message`].
+    pub fn ty(mut self, ty: ErrorType) -> Self {
+        self.ty = ty;
+        self
+    }
+
+    /// Sets the type of the error to [`ErrorType::Message`] with the given
+    /// message.
+    pub fn message(self, msg: &str) -> Self {
+        self.ty(ErrorType::Message(msg.to_owned()))
+    }
+
+    /// Adds a frame after the frames that were already added, like
+    /// [`EzError::add_frame`].
+    pub fn frame(mut self, loc: &'static ConstLocation) -> Self {
+        self.frames.push(loc);
+        self
+    }
+
+    /// Builds the error.
+    pub fn build(self) -> EzError {
+        let mut err = EzError::new(self.ty);
+        for loc in self.frames {
+            err.add_frame(loc);
+        }
+        err
+    }
+}
+
+impl core::fmt::Display for EzError {
+    /// Formats the error like [`EzError::render`]: the name and message
+    /// followed by every frame as `file:line:column (function)`.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.render())
+    }
+}
+
+/// Converts any error into an [`ErrorType::Conversion`] error that records
+/// the type name of the error, except for [`std::io::Error`], which keeps
+/// its [`ErrorKind`] as [`ErrorType::Io`].
+///
+/// Types that only implement [`Display`], e.g. `String` or `&str`, are not
+/// converted, because [`EzError`] implements [`Display`] itself and a
+/// conversion from every [`Display`] type would overlap with the conversion
+/// from [`EzError`] to itself. Convert them with [`EzError::message`]:
+///
+/// ```
+/// # use ez_err::prelude::*;
+/// let res: std::result::Result<u32, String> = Err("no such user".into());
+/// let err = res.map_err(|e| EzError::message(&e)).loc(flc!()).unwrap_err();
+/// assert_eq!("no such user", err.ty().to_string());
+/// ```
+///
+/// The error type has to be `'static`, because the conversion checks whether
+/// it is a [`std::io::Error`]. Error types that borrow data have to be
+/// converted explicitly as well.
+///
+/// [`ErrorKind`]: std::io::ErrorKind
+/// [`Display`]: core::fmt::Display
+#[cfg(feature = "std")]
+impl<E> From<E> for EzError
+where
+    E: std::error::Error + 'static,
+{
+    fn from(err: E) -> Self {
+        let any: &dyn std::any::Any = &err;
+        #[cfg_attr(not(feature = "std_backtrace"), allow(unused_mut))]
+        let mut ez = match any.downcast_ref::<std::io::Error>() {
+            Some(io) => EzError::new(ErrorType::Io(io.kind(), io.to_string())),
+            None => EzError::new(ErrorType::Conversion {
+                source_type: std::any::type_name::<E>(),
+                message: err.to_string(),
+            }),
+        };
+        #[cfg(feature = "std_backtrace")]
+        {
+            let backtrace = std::backtrace::Backtrace::force_capture();
+            ez.inner.backtrace = OsBacktrace(Some(std::sync::Arc::new(backtrace)));
+        }
+        ez
+    }
+}
+
+/// The different error types that can occur.
+///
+/// New variants are added in minor releases, so the enum is
+/// `#[non_exhaustive]` and a `match` on it outside of this crate needs a
+/// `_ =>` arm:
+///
+/// ```
+/// # use ez_err::prelude::*;
+/// fn describe(err: &EzError) -> &'static str {
+///     match err.ty() {
+///         ErrorType::NoneOption => "missing value",
+///         ErrorType::IndexOutOfBounds(..) | ErrorType::RangeOutOfBounds(..) => "bad index",
+///         _ => "other error",
+///     }
+/// }
+///
+/// assert_eq!("bad index", describe(&EzError::new(ErrorType::IndexOutOfBounds(3, 2))));
+/// assert_eq!("other error", describe(&EzError::message("boom")));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum ErrorType {
+    /// Wraps an internal error that is not compatible with the
+    /// custom error types by default.
+    Internal(String),
+
+    /// An error that occured where an `Option` was `None`.
+    NoneOption,
+
+    /// An error that occured when an array index was outside of the
+    /// valid range.
+    IndexOutOfBounds(usize, usize),
+
+    /// The range is larger than the array.
+    RangeOutOfBounds(usize, usize, usize),
+
+    /// The given range index is not valid (`end < start`).
+    InvalidRange,
+
+    /// An operation required at least one element, but the slice was empty.
+    EmptySlice,
+
+    /// The number of indices did not match the number of dimensions of
+    /// the shape (`indices`, `dimensions`).
+    ShapeMismatch(usize, usize),
+
+    /// An index was outside of the range of its dimension
+    /// (`dimension`, `index`, `len`).
+    DimensionOutOfBounds(usize, usize, usize),
+
+    /// An I/O error, e.g. from opening a file, with the kind of the error
+    /// (`kind`, `message`). Only available with the `std` feature. Since
+    /// `ErrorType` is `#[non_exhaustive]`, a `match` outside of this crate
+    /// always has a `_ =>` arm, so enabling the feature never breaks it.
+    #[cfg(feature = "std")]
+    Io(
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::io_kind"))]
+        std::io::ErrorKind,
+        String,
+    ),
+
+    /// A key was not present in a map (`key`). The key is stored as its
+    /// [`Debug`] representation.
+    ///
+    /// [`Debug`]: core::fmt::Debug
+    KeyNotFound(String),
+
+    /// A panic that was caught and converted with [`EzError::from_panic`]
+    /// (`message`).
+    Panic(String),
+    /// An operation did not finish within the given time (`timeout`).
+    Timeout(core::time::Duration),
+    /// A string could not be parsed into the target type (`input`, `target`).
+    /// Created by [`ParseExt::eparse`].
+    ///
+    /// [`ParseExt::eparse`]: crate::parse_ext::ParseExt::eparse
+    Parse {
+        /// The string that could not be parsed.
+        input: String,
+        /// The name of the type the string was parsed into.
+        // The full path keeps serde from borrowing the string from the
+        // deserializer, which would require `'de: 'static`; it is interned instead.
+        #[cfg_attr(
+            feature = "serde",
+            serde(deserialize_with = "crate::serde_impl::deserialize_static_str")
+        )]
+        target: &'static core::primitive::str,
+    },
+    /// A foreign error that was converted with the `From` implementation
+    /// (`source_type`, `message`). Unlike [`ErrorType::Internal`], which is
+    /// only created explicitly, this keeps the name of the original type.
+    Conversion {
+        /// The type name of the converted error, e.g.
+        /// `core::num::error::ParseIntError`.
+        // See `ErrorType::Parse::target` for why the path is spelled out.
+        #[cfg_attr(
+            feature = "serde",
+            serde(deserialize_with = "crate::serde_impl::deserialize_static_str")
+        )]
+        source_type: &'static core::primitive::str,
+        /// The message of the converted error.
+        message: String,
+    },
+    /// An integer was divided by zero. Created by [`CheckedExt::ediv`].
+    ///
+    /// [`CheckedExt::ediv`]: crate::checked_ext::CheckedExt::ediv
+    DivByZero,
+    /// The result of an integer operation does not fit into its type.
+    /// Created by the methods of [`CheckedExt`].
+    ///
+    /// [`CheckedExt`]: crate::checked_ext::CheckedExt
+    ArithmeticOverflow,
+    /// A resource of the application, e.g. a user or an order, does not
+    /// exist (`resource`). Created by [`EzError::not_found`].
+    NotFound {
+        /// A description of the missing resource, e.g. `user 42`.
+        resource: String,
+    },
+    /// Several errors that occurred together.
+    Multiple(Vec<EzError>),
+
+    /// A custom error with an attached message.
+    Message(String),
+
+    /// No error specified.
+    Custom {
+        /// A custom message code used for storing custom information.
+        code: u32,
+        /// The name of the error.
+        name: String,
+        /// The message of the error.
+        message: String,
+    },
+}
+
+impl ErrorType {
+    /// Formats the error type into a String for console output.
+    pub fn format(self) -> String {
+        match self {
+            ErrorType::Internal(msg) => msg,
+            #[cfg(feature = "std")]
+            ErrorType::Io(_, msg) => msg,
+            ErrorType::Panic(msg) => msg,
+            ErrorType::Conversion { message, .. } => message,
+            ErrorType::Message(msg) => msg,
+            ErrorType::Custom { message, .. } => message,
+            ty => ty.to_string(),
+        }
+    }
+
+    /// Returns the name of the `ErrorType` that should be included
+    /// in the stacktrace.
+    pub fn name(&self) -> &str {
+        match self {
+            ErrorType::Internal(_) => "WrappedInternal",
+            ErrorType::NoneOption => "NoneOption",
+            ErrorType::IndexOutOfBounds(_, _) => "IndexOutOfBounds",
+            ErrorType::RangeOutOfBounds(_, _, _) => "RangeOutOfBounds",
+            ErrorType::InvalidRange => "InvalidRange",
+            ErrorType::EmptySlice => "EmptySlice",
+            ErrorType::ShapeMismatch(_, _) => "ShapeMismatch",
+            ErrorType::DimensionOutOfBounds(_, _, _) => "DimensionOutOfBounds",
+            #[cfg(feature = "std")]
+            ErrorType::Io(_, _) => "Io",
+            ErrorType::KeyNotFound(_) => "KeyNotFound",
+            ErrorType::Panic(_) => "Panic",
+            ErrorType::Timeout(_) => "Timeout",
+            ErrorType::Parse { .. } => "Parse",
+            ErrorType::Conversion { .. } => "Conversion",
+            ErrorType::DivByZero => "DivByZero",
+            ErrorType::ArithmeticOverflow => "ArithmeticOverflow",
+            ErrorType::NotFound { .. } => "NotFound",
+            ErrorType::Multiple(_) => "Multiple",
+            ErrorType::Message(_) => "Message",
+            ErrorType::Custom { name, .. } => name,
+        }
+    }
+
+    /// Returns the HTTP status code that best describes the error type.
+    /// [`ErrorType::Custom`] errors use their code if it is a valid HTTP error
+    /// status (`400..=599`). [`ErrorType::NotFound`] errors are `404` and
+    /// [`ErrorType::Timeout`] errors are gateway timeouts (`504`). All other
+    /// errors are internal server errors (`500`).
+    pub fn http_status(&self) -> u16 {
+        match self {
+            ErrorType::NotFound { .. } => 404,
+            ErrorType::Timeout(_) => 504,
+            ErrorType::Custom { code, .. } if (400..=599).contains(code) => *code as u16,
+            _ => 500,
+        }
+    }
+
+    /// Returns `true` for error types defined by ez-err and `false` for
+    /// [`ErrorType::Message`] and [`ErrorType::Custom`]. Built-in errors often
+    /// point to a programming bug, while user errors are usually expected
+    /// domain errors. [`ErrorType::Multiple`] is built-in if all of its
+    /// errors are.
+    pub fn is_builtin(&self) -> bool {
+        match self {
+            ErrorType::Multiple(errors) => errors.iter().all(EzError::is_builtin),
+            ErrorType::Message(_) | ErrorType::Custom { .. } => false,
+            _ => true,
+        }
+    }
+
+    /// Returns a fixed label for the `ErrorType` that is suitable for metric
+    /// labels (e.g. Prometheus). Unlike [`name`], the set of possible values
+    /// is bounded: [`ErrorType::Message`] always maps to `"message"` and
+    /// [`ErrorType::Custom`] always maps to `"custom"`, regardless of their
+    /// contents.
+    ///
+    /// | Variant                | Label                       |
+    /// |------------------------|-----------------------------|
+    /// | `Internal`             | `"internal"`                |
+    /// | `NoneOption`           | `"none_option"`             |
+    /// | `IndexOutOfBounds`     | `"index_out_of_bounds"`     |
+    /// | `RangeOutOfBounds`     | `"range_out_of_bounds"`     |
+    /// | `InvalidRange`         | `"invalid_range"`           |
+    /// | `EmptySlice`           | `"empty_slice"`             |
+    /// | `ShapeMismatch`        | `"shape_mismatch"`          |
+    /// | `DimensionOutOfBounds` | `"dimension_out_of_bounds"` |
+    /// | `Io`                   | `"io"`                      |
+    /// | `KeyNotFound`          | `"key_not_found"`           |
+    /// | `Panic`                | `"panic"`                   |
+    /// | `Timeout`              | `"timeout"`                 |
+    /// | `Parse`                | `"parse"`                   |
+    /// | `Conversion`           | `"conversion"`              |
+    /// | `DivByZero`            | `"div_by_zero"`             |
+    /// | `ArithmeticOverflow`   | `"arithmetic_overflow"`     |
+    /// | `NotFound`             | `"not_found"`               |
+    /// | `Multiple`             | `"multiple"`                |
+    /// | `Message`              | `"message"`                 |
+    /// | `Custom`               | `"custom"`                  |
+    ///
+    /// [`name`]: ErrorType::name
+    pub fn metric_label(&self) -> &'static str {
+        match self {
+            ErrorType::Internal(_) => "internal",
+            ErrorType::NoneOption => "none_option",
+            ErrorType::IndexOutOfBounds(_, _) => "index_out_of_bounds",
+            ErrorType::RangeOutOfBounds(_, _, _) => "range_out_of_bounds",
+            ErrorType::InvalidRange => "invalid_range",
+            ErrorType::EmptySlice => "empty_slice",
+            ErrorType::ShapeMismatch(_, _) => "shape_mismatch",
+            ErrorType::DimensionOutOfBounds(_, _, _) => "dimension_out_of_bounds",
+            #[cfg(feature = "std")]
+            ErrorType::Io(_, _) => "io",
+            ErrorType::KeyNotFound(_) => "key_not_found",
+            ErrorType::Panic(_) => "panic",
+            ErrorType::Timeout(_) => "timeout",
+            ErrorType::Parse { .. } => "parse",
+            ErrorType::Conversion { .. } => "conversion",
+            ErrorType::DivByZero => "div_by_zero",
+            ErrorType::ArithmeticOverflow => "arithmetic_overflow",
+            ErrorType::NotFound { .. } => "not_found",
+            ErrorType::Multiple(_) => "multiple",
+            ErrorType::Message(_) => "message",
+            ErrorType::Custom { .. } => "custom",
+        }
+    }
+
+    /// Returns the code of an [`ErrorType::Custom`] error and `None` for all
+    /// built-in error types.
+    pub fn code(&self) -> Option<u32> {
+        match self {
+            ErrorType::Custom { code, .. } => Some(*code),
+            _ => None,
+        }
+    }
+
+    /// Returns a small integer that identifies the `ErrorType`, e.g. for
+    /// space constrained logs or counters. [`ErrorType::Custom`] returns its
+    /// `code`, all other types use the fixed codes below. The codes are
+    /// stable: new error types only ever get new codes.
+    ///
+    /// | `ErrorType`            | code   |
+    /// |------------------------|--------|
+    /// | `Internal`             | `0`    |
+    /// | `NoneOption`           | `1`    |
+    /// | `IndexOutOfBounds`     | `2`    |
+    /// | `RangeOutOfBounds`     | `3`    |
+    /// | `InvalidRange`         | `4`    |
+    /// | `EmptySlice`           | `5`    |
+    /// | `ShapeMismatch`        | `6`    |
+    /// | `DimensionOutOfBounds` | `7`    |
+    /// | `Multiple`             | `8`    |
+    /// | `Message`              | `9`    |
+    /// | `Io`                   | `10`   |
+    /// | `KeyNotFound`          | `11`   |
+    /// | `Panic`                | `12`   |
+    /// | `Timeout`              | `13`   |
+    /// | `Parse`                | `14`   |
+    /// | `Conversion`           | `15`   |
+    /// | `DivByZero`            | `16`   |
+    /// | `ArithmeticOverflow`   | `17`   |
+    /// | `NotFound`             | `18`   |
+    /// | `Custom`               | `code` |
+    ///
+    /// Custom codes are not checked against the built-in codes, so choose
+    /// them outside of the low range (e.g. `1000` and above) if the code has
+    /// to be decoded unambiguously.
+    pub fn numeric_code(&self) -> u32 {
+        match self {
+            ErrorType::Internal(_) => 0,
+            ErrorType::NoneOption => 1,
+            ErrorType::IndexOutOfBounds(_, _) => 2,
+            ErrorType::RangeOutOfBounds(_, _, _) => 3,
+            ErrorType::InvalidRange => 4,
+            ErrorType::EmptySlice => 5,
+            ErrorType::ShapeMismatch(_, _) => 6,
+            ErrorType::DimensionOutOfBounds(_, _, _) => 7,
+            ErrorType::Multiple(_) => 8,
+            ErrorType::Message(_) => 9,
+            #[cfg(feature = "std")]
+            ErrorType::Io(_, _) => 10,
+            ErrorType::KeyNotFound(_) => 11,
+            ErrorType::Panic(_) => 12,
+            ErrorType::Timeout(_) => 13,
+            ErrorType::Parse { .. } => 14,
+            ErrorType::Conversion { .. } => 15,
+            ErrorType::DivByZero => 16,
+            ErrorType::ArithmeticOverflow => 17,
+            ErrorType::NotFound { .. } => 18,
+            ErrorType::Custom { code, .. } => *code,
+        }
+    }
+}
+
+impl core::fmt::Display for ErrorType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ErrorType::Internal(msg) => f.write_str(msg),
+            #[cfg(feature = "std")]
+            ErrorType::NoneOption => {
+                let msg = *NONE_MESSAGE.read().unwrap_or_else(|e| e.into_inner());
+                f.write_str(msg.unwrap_or("Option was none"))
+            }
+            #[cfg(not(feature = "std"))]
+            ErrorType::NoneOption => f.write_str("Option was none"),
+            ErrorType::IndexOutOfBounds(idx, len) => {
+                write!(f, "Index {} was outside of the range 0..{}", idx, len)
+            }
+            ErrorType::RangeOutOfBounds(start, end, len) => {
+                write!(
+                    f,
+                    "Range {}..{} was larger than the array range 0..{}",
+                    start, end, len
+                )
+            }
+            ErrorType::InvalidRange => {
+                f.write_str("The provided range was invalid (end < start or X..=usize::MAX)")
+            }
+            ErrorType::EmptySlice => f.write_str("slice was empty"),
+            ErrorType::ShapeMismatch(indices, dims) => write!(
+                f,
+                "Got {} indices for a shape with {} dimensions",
+                indices, dims
+            ),
+            ErrorType::DimensionOutOfBounds(dim, idx, len) => write!(
+                f,
+                "Index {} of dimension {} was outside of the range 0..{}",
+                idx, dim, len
+            ),
+            #[cfg(feature = "std")]
+            ErrorType::Io(_, msg) => f.write_str(msg),
+            ErrorType::KeyNotFound(key) => write!(f, "Key {} was not found", key),
+            ErrorType::Panic(msg) => f.write_str(msg),
+            ErrorType::Timeout(dur) => write!(f, "Operation timed out after {:?}", dur),
+            ErrorType::Parse { input, target } => {
+                write!(f, "Could not parse {:?} as {}", input, target)
+            }
+            ErrorType::Conversion {
+                source_type,
+                message,
+            } => write!(f, "{} (from {})", message, source_type),
+            ErrorType::DivByZero => f.write_str("Division by zero"),
+            ErrorType::ArithmeticOverflow => f.write_str("Arithmetic overflow"),
+            ErrorType::NotFound { resource } => write!(f, "{} not found", resource),
+            ErrorType::Multiple(errors) => {
+                write!(f, "{} errors occurred", errors.len())?;
+                for e in errors {
+                    write!(f, "\n- {}: {}", e.ty().name(), e.ty())?;
+                }
+                Ok(())
+            }
+            ErrorType::Message(msg) => f.write_str(msg),
+            ErrorType::Custom { message, .. } => f.write_str(message),
+        }
+    }
+}
+
+/// Information about the location in a source file in a constant context.
+#[derive(Debug)]
+pub struct ConstLocation {
+    /// The file of the location.
+    pub file: &'static str,
+    /// The line of the location.
+    pub line: u32,
+    /// The column of the location.
+    pub column: u32,
+    module: &'static str,
+    function: Option<fn() -> &'static str>,
+}
+
+impl ConstLocation {
+    /// Creates a new [`ConstLocation`] using the given file and line.
+    pub const fn new(file: &'static str, line: u32, column: u32) -> ConstLocation {
+        ConstLocation {
+            file,
+            line,
+            column,
+            module: "",
+            function: None,
+        }
+    }
+
+    /// Sets the module path of the location. [`flc!`] fills it in using
+    /// [`module_path!`].
+    pub const fn with_module(mut self, module: &'static str) -> ConstLocation {
+        self.module = module;
+        self
+    }
+
+    /// Sets a function that returns the name of the function that contains
+    /// the location. The name can not be computed in a constant context, so
+    /// it is only resolved when [`ConstLocation::function`] is called.
+    /// [`flc!`] fills it in automatically.
+    pub const fn with_function(mut self, function: fn() -> &'static str) -> ConstLocation {
+        self.function = Some(function);
+        self
+    }
+
+    /// Returns the path of the module that contains the location, e.g.
+    /// `my_crate::storage`, or an empty string if it is unknown.
+    pub fn module(&self) -> &'static str {
+        self.module
+    }
+
+    /// Returns the path of the function that contains the location, e.g.
+    /// `my_crate::storage::load`, if it is known.
+    pub fn function(&self) -> Option<&'static str> {
+        self.function.map(|f| f())
+    }
+}
+
+impl PartialEq for ConstLocation {
+    fn eq(&self, other: &Self) -> bool {
+        self.file == other.file
+            && self.line == other.line
+            && self.column == other.column
+            && self.module == other.module
+            && self.function() == other.function()
+    }
+}
+
+/// Returns the path of the function that contains the item `f`, which is
+/// defined inside of [`flc!`]. Used by [`flc!`] to capture the function name.
+#[doc(hidden)]
+pub fn function_name<F>(_: F) -> &'static str {
+    let mut name = core::any::type_name::<F>();
+    // Strip the items that are defined by `flc!` itself.
+    for _ in 0..3 {
+        name = name.rsplit_once("::").map_or(name, |(path, _)| path);
+    }
+    while let Some(path) = name.strip_suffix("::{{closure}}") {
+        name = path;
+    }
+    name
+}
+
+#[cfg(feature = "std")]
+type LocationKey = (&'static str, u32, u32, &'static str);
+
+#[cfg(feature = "std")]
+static INTERNED_LOCATIONS: Mutex<BTreeMap<LocationKey, &'static ConstLocation>> =
+    Mutex::new(BTreeMap::new());
+
+/// Returns a `'static` [`ConstLocation`] for a location that is only known at
+/// runtime. Each distinct location is allocated once and reused afterwards.
+#[cfg(feature = "std")]
+pub(crate) fn intern_location(
+    file: &'static str,
+    line: u32,
+    column: u32,
+    module: &'static str,
+) -> &'static ConstLocation {
+    try_intern_location(file, line, column, module, usize::MAX)
+        .expect("the number of interned locations is unlimited")
+}
+
+/// Returns a `'static` [`ConstLocation`] like [`intern_location`], or `None`
+/// if the location is new and `limit` locations are interned already.
+#[cfg(feature = "std")]
+pub(crate) fn try_intern_location(
+    file: &'static str,
+    line: u32,
+    column: u32,
+    module: &'static str,
+    limit: usize,
+) -> Option<&'static ConstLocation> {
+    let mut interned = INTERNED_LOCATIONS.lock().unwrap_or_else(|e| e.into_inner());
+    let len = interned.len();
+    match interned.entry((file, line, column, module)) {
+        Entry::Occupied(loc) => Some(*loc.get()),
+        Entry::Vacant(_) if len >= limit => None,
+        Entry::Vacant(slot) => Some(*slot.insert(Box::leak(Box::new(
+            ConstLocation::new(file, line, column).with_module(module),
+        )))),
+    }
+}
+
+/// Extension for `Result<T>` to allow for custom error handling.
+///
+/// Only [`LocData::loc`] has to be implemented. The other methods have
+/// default implementations that fall back to it and drop the extra data
+/// they are given, e.g. the tag of [`LocData::loc_tagged`]. Implement them
+/// as well to keep it.
+pub trait LocData<T>: Sized {
+    /// The return type of `add_info`. This can be used to convert
+    /// between different error types.
+    type Result;
+
+    /// Adds a new frame info to the [`Result<T>`]. This only happens
+    /// when the [`Result<T>`] is [`Err(T)`]. Commonly used with the [`flc!`] macro.
+    fn loc(self, flc: &'static ConstLocation) -> Self::Result;
+
+    /// Adds a new frame info like [`loc`] together with a tag that is printed
+    /// next to the frame, see [`EzError::add_tagged_frame`].
+    ///
+    /// **The default implementation drops the tag.** It only calls [`loc`],
+    /// so the frame is printed without the tag. Implement this method to
+    /// keep it.
+    ///
+    /// [`loc`]: LocData::loc
+    fn loc_tagged(self, flc: &'static ConstLocation, tag: u64) -> Self::Result {
+        let _ = tag;
+        self.loc(flc)
+    }
+
+    /// Adds a new frame info like [`loc`] together with a note that is printed
+    /// next to the frame, see [`EzError::add_noted_frame`]. The note is not
+    /// copied, so no allocation happens beyond the frame itself.
+    ///
+    /// **The default implementation drops the note.** It only calls [`loc`],
+    /// so the frame is printed without the note. Implement this method to
+    /// keep it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// let res: Result<()> = Err(EzError::message("denied"));
+    /// let err = res.loc_note(flc!(), "opening the config").unwrap_err();
+    /// assert!(err.trace_string().ends_with(" - opening the config\n"));
+    /// ```
+    ///
+    /// [`loc`]: LocData::loc
+    fn loc_note(self, flc: &'static ConstLocation, note: &'static str) -> Self::Result {
+        let _ = note;
+        self.loc(flc)
+    }
+
+    /// Adds a new frame info like [`loc`] in debug builds and skips it in
+    /// release builds (without `debug_assertions`). This removes the frames
+    /// of individual call sites from release binaries, while the
+    /// `no_stacktrace` feature removes all of them. The default
+    /// implementation calls [`loc`] in all builds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// let res: Result<()> = Err(EzError::message("denied"));
+    /// let err = res.loc_debug(flc!()).unwrap_err();
+    /// assert_eq!(cfg!(debug_assertions), !err.frames().is_empty());
+    /// ```
+    ///
+    /// [`loc`]: LocData::loc
+    fn loc_debug(self, flc: &'static ConstLocation) -> Self::Result {
+        self.loc(flc)
+    }
+
+    /// Adds a new frame info like [`loc`] to an error with the type built by
+    /// `f`. `None` is replaced by the new error. A foreign error is replaced
+    /// as well, but kept as a cause (see [`EzError::because`]). An
+    /// [`EzError`] already describes what went wrong, so it is kept as it is
+    /// and only gets the frame. The closure is only called if a new error is
+    /// created, so expensive messages are not built on the success path.
+    ///
+    /// **The default implementation never calls `f`.** It falls back to
+    /// [`loc`], so the error gets the type that [`loc`] gives it. Implement
+    /// this method to use the type built by `f`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// let ids = [3, 5, 8];
+    /// let err = ids
+    ///     .iter()
+    ///     .position(|&id| id == 4)
+    ///     .loc_or_else(|| ErrorType::Message(format!("no id 4 in {:?}", ids)), flc!())
+    ///     .unwrap_err();
+    /// assert_eq!(&ErrorType::Message("no id 4 in [3, 5, 8]".into()), err.ty());
+    /// ```
+    ///
+    /// [`loc`]: LocData::loc
+    fn loc_or_else<F>(self, f: F, flc: &'static ConstLocation) -> Self::Result
+    where
+        F: FnOnce() -> ErrorType,
+    {
+        let _ = f;
+        self.loc(flc)
+    }
+}
+
+/// Extension for [`Result<T>`] to enrich errors while they are propagated.
+pub trait ResultExt<T> {
+    /// Adds a new frame info like [`LocData::loc`] and attaches a context
+    /// message (see [`EzError::context`]) to the error. Context messages are
+    /// kept even if the `no_stacktrace` feature is enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// fn load_user(id: u32) -> Result<String> {
+    ///     bail!("database is offline")
+    /// }
+    ///
+    /// let err = load_user(7).ctx("loading user 7", flc!()).unwrap_err();
+    /// assert_eq!(&["loading user 7".to_owned()], err.contexts());
+    /// ```
+    fn ctx(self, msg: &str, flc: &'static ConstLocation) -> Self;
+
+    /// Adds a new frame info like [`LocData::loc`] and attaches the context
+    /// message only if the type of the error matches the predicate. This
+    /// allows adding context to e.g. converted [`ErrorType::Conversion`] errors
+    /// while passing the own errors through unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// # #[cfg(feature = "std")] {
+    /// fn parse_port(port: &str) -> Result<u16> {
+    ///     port.parse::<u16>().loc(flc!())
+    /// }
+    ///
+    /// let err = parse_port("http")
+    ///     .context_if(|ty| matches!(ty, ErrorType::Conversion { .. }), "parsing the port", flc!())
+    ///     .unwrap_err();
+    /// assert_eq!(&["parsing the port".to_owned()], err.contexts());
+    /// # }
+    /// ```
+    fn context_if<F>(self, predicate: F, msg: &str, flc: &'static ConstLocation) -> Self
+    where
+        F: Fn(&ErrorType) -> bool;
+}
+
+impl<T> ResultExt<T> for Result<T> {
+    fn ctx(self, msg: &str, flc: &'static ConstLocation) -> Self {
+        self.map_err(|mut err| {
+            err.add_frame(flc);
+            err.context(msg)
+        })
+    }
+
+    fn context_if<F>(self, predicate: F, msg: &str, flc: &'static ConstLocation) -> Self
+    where
+        F: Fn(&ErrorType) -> bool,
+    {
+        self.map_err(|mut err| {
+            err.add_frame(flc);
+            if predicate(err.ty()) {
+                err.context(msg)
+            } else {
+                err
+            }
+        })
+    }
+}
+
+/// Extension for `Vec<Result<T>>` to process all results at once.
+pub trait ResultVecExt<T> {
+    /// Returns all values if every result is [`Ok`]. Otherwise returns an
+    /// [`ErrorType::Multiple`] that contains every error together with its
+    /// frames, unlike `collect::<Result<Vec<_>>>()` which stops at the first one.
+    fn collect_all(self) -> Result<Vec<T>>;
+
+    /// Handles every result like [`Handle::handle`], so each error is
+    /// reported on its own, and returns the values of the [`Ok`] results.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use ez_err::prelude::*;
+    /// let results = vec![Ok(1), Err(EzError::message("skipped")), Ok(3)];
+    /// assert_eq!(vec![1, 3], results.handle_all());
+    /// ```
+    #[cfg(feature = "std")]
+    fn handle_all(self) -> Vec<T>;
+}
+
+impl<T> ResultVecExt<T> for Vec<Result<T>> {
+    fn collect_all(self) -> Result<Vec<T>> {
+        self.into_iter().collect_errors()
+    }
+
+    #[cfg(feature = "std")]
+    fn handle_all(self) -> Vec<T> {
+        self.into_iter().filter_map(Handle::handle).collect()
+    }
+}
+
+/// Extension for iterators over [`Result<T>`] to process all results at once.
+pub trait ResultIterExt<T> {
+    /// Returns all values if every result is [`Ok`]. Otherwise returns the
+    /// errors combined with [`EzError::aggregate`]. Unlike
+    /// `collect::<Result<Vec<_>>>()`, the iterator is always consumed fully.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// # #[cfg(feature = "std")] {
+    /// let inputs = ["1", "x", "3", "y"];
+    /// let err = inputs
+    ///     .iter()
+    ///     .map(|s| s.parse::<u32>().loc(flc!()))
+    ///     .collect_errors()
+    ///     .unwrap_err();
+    /// assert_eq!(2, err.parts_iter().count());
+    /// # }
+    /// ```
+    fn collect_errors(self) -> Result<Vec<T>>;
+}
+
+impl<T, I> ResultIterExt<T> for I
+where
+    I: Iterator<Item = Result<T>>,
+{
+    fn collect_errors(self) -> Result<Vec<T>> {
+        let mut values = Vec::with_capacity(self.size_hint().0);
+        let mut errors = Vec::new();
+        for result in self {
+            match result {
+                Ok(v) => values.push(v),
+                Err(e) => errors.push(e),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(values)
+        } else {
+            Err(EzError::aggregate(errors))
+        }
+    }
+}
+
+/// Extension for [`Option<T>`] to describe which value was missing.
+pub trait OptionExt<T> {
+    /// Converts `None` into an [`ErrorType::Message`] error with the given
+    /// message instead of the generic [`ErrorType::NoneOption`] and adds a
+    /// new frame info like [`LocData::loc`]. `Some` is passed through.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// let args = ["--port"];
+    /// let err = args.get(1).loc_msg("missing value for --port", flc!()).unwrap_err();
+    /// assert_eq!(&ErrorType::Message("missing value for --port".into()), err.ty());
+    /// ```
+    fn loc_msg(self, msg: &str, flc: &'static ConstLocation) -> Result<T>;
+}
+
+impl<T> OptionExt<T> for Option<T> {
+    #[inline(always)]
+    fn loc_msg(self, msg: &str, flc: &'static ConstLocation) -> Result<T> {
+        self.ok_or_else(|| {
+            let mut err = EzError::message(msg);
+            err.add_frame(flc);
+            err
+        })
+    }
+}
+
+/// Extension for `Result<T>` to allow for custom error handling.
+///
+/// Only [`Handle::handle_with`] has to be implemented. The methods that
+/// need the `std` feature have default implementations, so enabling the
+/// feature never breaks an implementation of the trait.
+pub trait Handle<T>: Sized {
+    /// Handles the result. If it contains an error a backtrace is
+    /// created and the error is printed to stderr.
+    ///
+    /// With the `tracing` feature the error is emitted as an event with the
+    /// `error.name`, `error.message` and `error.trace` fields instead:
+    ///
+    /// ```ignore
+    /// tracing_subscriber::fmt().json().init();
+    ///
+    /// let res: Result<()> = Err(EzError::message("disk full")).loc(flc!());
+    /// // {"level":"ERROR","fields":{"message":"Error Message: disk full",
+    /// //  "error.name":"Message","error.message":"disk full",
+    /// //  "error.trace":"src/main.rs:4:62 (app::main)\n"},...}
+    /// res.handle();
+    /// ```
+    #[cfg(feature = "std")]
+    fn handle(self) -> Option<T> {
+        self.handle_with_options(&HandleOptions::default())
+    }
+
+    /// Handles the result like [`handle`] but renders the error using
+    /// the given [`HandleOptions`].
+    ///
+    /// [`handle`]: Handle::handle
+    #[cfg(feature = "std")]
+    fn handle_with_options(self, opts: &HandleOptions) -> Option<T> {
+        self.handle_with(|e| e.emit(opts))
+    }
+
+    /// Prints the error like [`handle`] but returns the result unchanged, so
+    /// the error can still be propagated with `?` after it was logged.
+    /// Only available if the type converts into a [`Result<T>`], like
+    /// `Result<T>` itself.
+    ///
+    /// [`handle`]: Handle::handle
+    #[cfg(feature = "std")]
+    fn log_err(self) -> Result<T>
+    where
+        Self: Into<Result<T>>,
+    {
+        let res = self.into();
+        if let Err(e) = &res {
+            e.emit(&HandleOptions::default());
+        }
+        res
+    }
+
+    /// Handles the result by passing the error to the given function instead
+    /// of printing it, e.g. to write it to a file or a custom logger.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// let res: Result<i32> = Err(EzError::message("disk full")).loc(flc!());
+    ///
+    /// let mut log = Vec::new();
+    /// assert_eq!(None, res.handle_with(|err| log.push(err.render())));
+    /// assert!(log[0].starts_with("Error Message: disk full"));
+    /// ```
+    fn handle_with<F: FnOnce(&EzError)>(self, f: F) -> Option<T>;
+
+    /// Handles the result by writing the error as a single line of JSON (see
+    /// [`EzError::to_json`]) followed by a newline to the writer, which
+    /// produces newline-delimited JSON. Errors of the writer are ignored.
+    #[cfg(feature = "std")]
+    fn handle_json_to<W: std::io::Write>(self, w: &mut W) -> Option<T> {
+        self.handle_with(|e| {
+            let _ = writeln!(w, "{}", e.to_json());
+        })
+    }
+
+    /// Handles the result like [`handle`] but emits the error as a single
+    /// line of JSON (see [`EzError::to_json`]) instead of the human-readable
+    /// text, e.g. for structured logging pipelines.
+    ///
+    /// [`handle`]: Handle::handle
+    #[cfg(feature = "std")]
+    fn handle_json(self) -> Option<T> {
+        self.handle_with(|e| {
+            let json = e.to_json();
+            e.emit_output(json.clone(), &HandleOptions::default(), move || json)
+        })
+    }
+
+    /// Handles the result or panics if it is [`Err`]. If it contains
+    /// an error a backtrace is created and the error is printed to the console.
+    #[cfg(feature = "std")]
+    fn handle_or_panic(self) -> T {
+        match self.handle() {
+            Some(v) => v,
+            None => panic!(),
+        }
+    }
+
+    /// Handles the result like [`handle`] and returns the matching exit code:
+    /// [`ExitCode::SUCCESS`] for [`Ok`] and [`EzError::exit_code`] for an
+    /// error. This is useful at the end of a `main` that returns an
+    /// [`ExitCode`].
+    ///
+    /// ```no_run
+    /// # use ez_err::prelude::*;
+    /// # use std::process::ExitCode;
+    /// # fn run() -> Result<()> { Ok(()) }
+    /// fn main() -> ExitCode {
+    ///     run().handle_exit()
+    /// }
+    /// ```
+    ///
+    /// [`handle`]: Handle::handle
+    /// [`ExitCode`]: std::process::ExitCode
+    /// [`ExitCode::SUCCESS`]: std::process::ExitCode::SUCCESS
+    #[cfg(feature = "std")]
+    fn handle_exit(self) -> std::process::ExitCode {
+        let mut code = std::process::ExitCode::SUCCESS;
+        self.handle_with(|e| {
+            code = e.exit_code();
+            e.emit(&HandleOptions::default());
+        });
+        code
+    }
+}
+
+impl<T> LocData<T> for Result<T> {
+    type Result = Result<T>;
+
+    #[inline(always)]
+    fn loc(mut self, loc: &'static ConstLocation) -> Self::Result {
+        if let Err(err) = &mut self {
+            err.add_frame(loc);
+        }
+
+        self
+    }
+
+    #[inline(always)]
+    fn loc_tagged(mut self, loc: &'static ConstLocation, tag: u64) -> Self::Result {
+        if let Err(err) = &mut self {
+            err.add_tagged_frame(loc, tag);
+        }
+
+        self
+    }
+
+    #[inline(always)]
+    fn loc_note(mut self, loc: &'static ConstLocation, note: &'static str) -> Self::Result {
+        if let Err(err) = &mut self {
+            err.add_noted_frame(loc, note);
+        }
+
+        self
+    }
+
+    #[inline(always)]
+    fn loc_debug(self, loc: &'static ConstLocation) -> Self::Result {
+        if cfg!(debug_assertions) {
+            self.loc(loc)
+        } else {
+            self
+        }
+    }
+
+    #[inline(always)]
+    fn loc_or_else<F>(self, _: F, loc: &'static ConstLocation) -> Self::Result
+    where
+        F: FnOnce() -> ErrorType,
+    {
+        self.loc(loc)
+    }
+}
+
+impl<T> Handle<T> for Result<T> {
+    fn handle_with<F: FnOnce(&EzError)>(self, f: F) -> Option<T> {
+        match self {
+            Ok(v) => Some(v),
+            Err(e) => {
+                f(&e);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, E> LocData<T> for std::result::Result<T, E>
 where
-    E: std::fmt::Display,
+    E: std::error::Error + 'static,
 {
     type Result = Result<T>;
 
     #[inline(always)]
     fn loc(self, loc: &'static ConstLocation) -> Self::Result {
-        #[cfg(not(feature = "no_stacktrace"))]
         match self {
             Ok(v) => Ok(v),
             Err(e) => Err({
@@ -303,56 +2421,1090 @@ where
                 err
             }),
         }
-        #[cfg(feature = "no_stacktrace")]
-        self
-    }
-}
+    }
+
+    #[inline(always)]
+    fn loc_tagged(self, loc: &'static ConstLocation, tag: u64) -> Self::Result {
+        self.map_err(|e| {
+            let mut err: EzError = e.into();
+            err.add_tagged_frame(loc, tag);
+            err
+        })
+    }
+
+    #[inline(always)]
+    fn loc_note(self, loc: &'static ConstLocation, note: &'static str) -> Self::Result {
+        self.map_err(|e| {
+            let mut err: EzError = e.into();
+            err.add_noted_frame(loc, note);
+            err
+        })
+    }
+
+    #[inline(always)]
+    fn loc_debug(self, loc: &'static ConstLocation) -> Self::Result {
+        if cfg!(debug_assertions) {
+            self.loc(loc)
+        } else {
+            self.map_err(EzError::from)
+        }
+    }
+
+    #[inline(always)]
+    fn loc_or_else<F>(self, f: F, loc: &'static ConstLocation) -> Self::Result
+    where
+        F: FnOnce() -> ErrorType,
+    {
+        self.map_err(|e| {
+            let mut err = EzError::new(f()).because(e);
+            err.add_frame(loc);
+            err
+        })
+    }
+}
+
+/// Extension for results with a foreign error type to choose the
+/// [`ErrorType`] of the error instead of converting it into
+/// [`ErrorType::Conversion`].
+pub trait LocMap<T, E> {
+    /// Maps the error with `f` into an [`ErrorType`] and adds a new frame info
+    /// like [`LocData::loc`]. This keeps structured information of the
+    /// foreign error that would be lost when it is converted into a string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// fn parse_port(port: &str) -> Result<u16> {
+    ///     port.parse::<u16>().loc_map(
+    ///         |e| ErrorType::Custom {
+    ///             code: 400,
+    ///             name: "InvalidPort".to_owned(),
+    ///             message: format!("{:?}", e.kind()),
+    ///         },
+    ///         flc!(),
+    ///     )
+    /// }
+    ///
+    /// let err = parse_port("http").unwrap_err();
+    /// assert_eq!("InvalidPort", err.ty().name());
+    /// assert_eq!(&ErrorType::Custom {
+    ///     code: 400,
+    ///     name: "InvalidPort".to_owned(),
+    ///     message: "InvalidDigit".to_owned(),
+    /// }, err.ty());
+    /// ```
+    fn loc_map<F>(self, f: F, flc: &'static ConstLocation) -> Result<T>
+    where
+        F: FnOnce(E) -> ErrorType;
+}
+
+impl<T, E> LocMap<T, E> for core::result::Result<T, E> {
+    #[inline(always)]
+    fn loc_map<F>(self, f: F, flc: &'static ConstLocation) -> Result<T>
+    where
+        F: FnOnce(E) -> ErrorType,
+    {
+        self.map_err(|e| {
+            let mut err = EzError::new(f(e));
+            err.add_frame(flc);
+            err
+        })
+    }
+}
+
+impl<T> LocData<T> for Option<T> {
+    type Result = Result<T>;
+
+    #[inline(always)]
+    fn loc(self, loc: &'static ConstLocation) -> Self::Result {
+        match self {
+            Some(v) => Ok(v),
+            None => Err({
+                let mut err = EzError::new(ErrorType::NoneOption);
+                err.add_frame(loc);
+                err
+            }),
+        }
+    }
+
+    #[inline(always)]
+    fn loc_tagged(self, loc: &'static ConstLocation, tag: u64) -> Self::Result {
+        self.ok_or_else(|| {
+            let mut err = EzError::new(ErrorType::NoneOption);
+            err.add_tagged_frame(loc, tag);
+            err
+        })
+    }
+
+    #[inline(always)]
+    fn loc_note(self, loc: &'static ConstLocation, note: &'static str) -> Self::Result {
+        self.ok_or_else(|| {
+            let mut err = EzError::new(ErrorType::NoneOption);
+            err.add_noted_frame(loc, note);
+            err
+        })
+    }
+
+    #[inline(always)]
+    fn loc_debug(self, loc: &'static ConstLocation) -> Self::Result {
+        if cfg!(debug_assertions) {
+            self.loc(loc)
+        } else {
+            self.ok_or_else(|| EzError::new(ErrorType::NoneOption))
+        }
+    }
+
+    #[inline(always)]
+    fn loc_or_else<F>(self, f: F, loc: &'static ConstLocation) -> Self::Result
+    where
+        F: FnOnce() -> ErrorType,
+    {
+        self.ok_or_else(|| {
+            let mut err = EzError::new(f());
+            err.add_frame(loc);
+            err
+        })
+    }
+}
+
+#[cfg(all(test, not(feature = "panic_on_error")))]
+mod tests {
+    use super::*;
+    use core::time::Duration;
+
+    #[test]
+    fn correct_info() {
+        let err: Result<()> = Err(EzError::message("test")).loc(flc!());
+        let (file, line) = (file!(), line!());
+
+        let loc = err.err().unwrap().frames()[0];
+        assert_eq!(loc.file, file);
+        assert_eq!(loc.line, line - 1);
+        assert_eq!(loc.column, 65);
+    }
+
+    #[test]
+    fn correct_bail() {
+        let inner_line = line!() + 2;
+        fn inner() -> Result<()> {
+            bail!("bailed");
+
+            Ok(())
+        }
+
+        let err = inner().err().unwrap();
+        assert_eq!(&ErrorType::Message("bailed".into()), err.ty());
+        assert_eq!(inner_line, err.frames()[0].line);
+    }
+
+    #[test]
+    fn correct_etry() {
+        let inner_line = line!() + 2;
+        fn inner(value: Option<u32>) -> Result<u32> {
+            let value = etry!(value);
+
+            Ok(value + 1)
+        }
+
+        assert_eq!(Ok(2), inner(Some(1)));
+        let err = inner(None).err().unwrap();
+        assert_eq!(&ErrorType::NoneOption, err.ty());
+        assert_eq!(1, err.frames().len());
+        assert_eq!(file!(), err.frames()[0].file);
+        assert_eq!(inner_line, err.frames()[0].line);
+        assert_eq!(25, err.frames()[0].column);
+    }
+
+    #[test]
+    fn correct_ensure() {
+        let inner_line = line!() + 2;
+        fn inner() -> Result<()> {
+            ensure!(false, "bad {}", 1);
+
+            Ok(())
+        }
 
-impl<T> LocData<T> for Option<T> {
-    type Result = Result<T>;
+        fn passing() -> Result<()> {
+            ensure!(1 + 1 == 2, "math is broken");
 
-    #[inline(always)]
-    fn loc(self, loc: &'static ConstLocation) -> Self::Result {
-        #[cfg(not(feature = "no_stacktrace"))]
-        match self {
-            Some(v) => Ok(v),
-            None => Err({
-                let mut err = EzError::new(ErrorType::NoneOption);
-                err.add_frame(loc);
-                err
-            }),
+            Ok(())
         }
-        #[cfg(feature = "no_stacktrace")]
-        self
+
+        let err = inner().err().unwrap();
+        assert_eq!(&ErrorType::Message("bad 1".into()), err.ty());
+        assert_eq!(inner_line, err.frames()[0].line);
+        assert_eq!(Ok(()), passing());
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn metric_labels() {
+        let custom = EzError::custom(7, "SomeUserName".into(), "details".into());
+        let msg = EzError::message("a very specific message");
+        let oob = EzError::new(ErrorType::IndexOutOfBounds(3, 3));
+
+        assert_eq!("custom", custom.metric_label());
+        assert_eq!("message", msg.metric_label());
+        assert_eq!("index_out_of_bounds", oob.metric_label());
+    }
 
     #[test]
-    fn correct_info() {
-        let err: Result<()> = Err(EzError::message("test")).loc(flc!());
-        let (file, line) = (file!(), line!());
+    fn input_snapshot() {
+        let err = EzError::message("bad input").with_input(&(1, "two"));
+        assert_eq!(Some("(1, \"two\")"), err.input());
 
-        let loc = err.err().unwrap().frames()[0];
-        assert_eq!(loc.file, file);
-        assert_eq!(loc.line, line - 1);
-        assert_eq!(loc.column, 65);
+        let long = "x".repeat(MAX_INPUT_LEN * 2);
+        let err = EzError::message("bad input").with_input(&long);
+        assert_eq!(MAX_INPUT_LEN + 3, err.input().unwrap().len());
+        assert!(err.input().unwrap().ends_with("..."));
     }
 
+    #[cfg(feature = "std")]
     #[test]
-    fn correct_bail() {
+    fn timed_error() {
+        let err = run_timed(|| -> Result<()> { Err(EzError::message("slow")) })
+            .err()
+            .unwrap();
+        assert_eq!(1, err.contexts().len());
+        assert!(err.contexts()[0].starts_with("failed after "));
+
+        assert_eq!(Some(3), run_timed(|| Ok(3)).ok());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn report_once() {
+        let mut err = EzError::message("reported");
+        assert!(!err.is_reported());
+        assert!(err
+            .output(&HandleOptions::default())
+            .contains("Stacktrace:"));
+
+        err.report();
+        assert!(err.is_reported());
+        assert_eq!(
+            "Error Message (already reported)",
+            err.output(&HandleOptions::default())
+        );
+
+        assert_eq!(None, Err::<(), _>(err).handle());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn ring_log_keeps_options() {
+        let ring_log = std::sync::Arc::new(crate::ring_log::RingLog::new(1));
+        let opts = HandleOptions {
+            color: true,
+            ring_log: Some(ring_log.clone()),
+            ..HandleOptions::default()
+        };
+
+        Err::<(), _>(EzError::message("colored")).handle_with_options(&opts);
+        assert!(ring_log.dump()[0].contains("\x1b["));
+    }
+
+    #[test]
+    fn correct_bail_custom() {
         let inner_line = line!() + 2;
         fn inner() -> Result<()> {
-            bail!("bailed");
+            bail_custom!(code = 42, name = "Custom", "bailed {}", 7);
 
             Ok(())
         }
 
         let err = inner().err().unwrap();
-        assert_eq!(&ErrorType::Message("bailed".into()), err.ty());
+        assert_eq!(
+            &ErrorType::Custom {
+                code: 42,
+                name: "Custom".into(),
+                message: "bailed 7".into(),
+            },
+            err.ty()
+        );
         assert_eq!(inner_line, err.frames()[0].line);
     }
+
+    #[test]
+    fn builtin_errors() {
+        assert!(EzError::new(ErrorType::IndexOutOfBounds(3, 3)).is_builtin());
+        assert!(!EzError::custom(1, "Domain".into(), "expected".into()).is_builtin());
+        assert!(!EzError::message("expected").is_builtin());
+    }
+
+    #[test]
+    fn collect_all_errors() {
+        let results: Vec<Result<i32>> = vec![
+            Ok(1),
+            Err(EzError::message("first")).loc(flc!()),
+            Ok(3),
+            Err(EzError::new(ErrorType::NoneOption)).loc(flc!()),
+        ];
+
+        let err = results.collect_all().err().unwrap();
+        match err.ty() {
+            ErrorType::Multiple(errors) => {
+                assert_eq!(2, errors.len());
+                assert_eq!(&ErrorType::Message("first".into()), errors[0].ty());
+                assert_eq!(&ErrorType::NoneOption, errors[1].ty());
+                assert_eq!(1, errors[1].frames().len());
+            }
+            ty => panic!("unexpected error type {:?}", ty),
+        }
+
+        let results: Vec<Result<i32>> = vec![Ok(1), Ok(2)];
+        assert_eq!(Ok(vec![1, 2]), results.collect_all());
+    }
+
+    #[cfg(not(feature = "single_frame"))]
+    #[cfg(feature = "std")]
+    #[test]
+    fn std_location() {
+        #[track_caller]
+        fn caller() -> &'static std::panic::Location<'static> {
+            std::panic::Location::caller()
+        }
+
+        let (first, second) = (caller(), caller());
+        let mut err = EzError::message("tracked");
+        err.add_std_location(first);
+        err.add_std_location(second);
+        err.add_std_location(first);
+
+        let frames = err.frames();
+        assert_eq!(3, frames.len());
+        assert_eq!(file!(), frames[0].file);
+        assert_eq!(first.line(), frames[0].line);
+        assert_eq!(first.column(), frames[0].column);
+        assert_eq!(second.line(), frames[1].line);
+        assert!(std::ptr::eq(frames[0], frames[2]));
+    }
+
+    #[cfg(not(feature = "single_frame"))]
+    #[test]
+    fn unique_frames() {
+        const LOOP: ConstLocation = ConstLocation::new("src/loop.rs", 3, 9);
+        const OUTER: ConstLocation = ConstLocation::new("src/outer.rs", 12, 5);
+
+        let mut err = EzError::message("looped");
+        for _ in 0..4 {
+            err.add_frame(&LOOP);
+        }
+        err.add_frame(&OUTER);
+        err.add_frame(&LOOP);
+
+        let unique = err.unique_frames();
+        assert_eq!(2, unique.len());
+        assert_eq!((&LOOP, 5), unique[0]);
+        assert_eq!((&OUTER, 1), unique[1]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn numeric_codes() {
+        let types = vec![
+            ErrorType::Internal("io".into()),
+            ErrorType::NoneOption,
+            ErrorType::IndexOutOfBounds(3, 2),
+            ErrorType::RangeOutOfBounds(1, 5, 2),
+            ErrorType::InvalidRange,
+            ErrorType::EmptySlice,
+            ErrorType::ShapeMismatch(1, 2),
+            ErrorType::DimensionOutOfBounds(0, 4, 2),
+            ErrorType::Multiple(Vec::new()),
+            ErrorType::Message("msg".into()),
+            ErrorType::Io(std::io::ErrorKind::NotFound, "io".into()),
+            ErrorType::KeyNotFound("\"id\"".into()),
+        ];
+        let codes: Vec<u32> = types.iter().map(ErrorType::numeric_code).collect();
+        assert_eq!((0..12).collect::<Vec<u32>>(), codes);
+
+        let err = EzError::custom(1042, "Quota".into(), "quota exceeded".into());
+        assert_eq!(1042, err.numeric_code());
+    }
+
+    #[test]
+    fn same_root() {
+        const ORIGIN: ConstLocation = ConstLocation::new("src/db.rs", 40, 9);
+        const ORIGIN_COLUMN: ConstLocation = ConstLocation::new("src/db.rs", 40, 31);
+        const API: ConstLocation = ConstLocation::new("src/api.rs", 7, 5);
+        const JOB: ConstLocation = ConstLocation::new("src/job.rs", 90, 13);
+
+        let mut a = EzError::message("connection 1 refused");
+        a.add_frame(&ORIGIN);
+        a.add_frame(&API);
+        let mut b = EzError::message("connection 2 refused");
+        b.add_frame(&ORIGIN_COLUMN);
+        b.add_frame(&JOB);
+        assert!(a.same_root(&b));
+
+        let mut other_origin = EzError::message("connection 1 refused");
+        other_origin.add_frame(&API);
+        assert!(!a.same_root(&other_origin));
+
+        let mut other_kind = EzError::new(ErrorType::NoneOption);
+        other_kind.add_frame(&ORIGIN);
+        assert!(!a.same_root(&other_kind));
+    }
+
+    #[cfg(not(feature = "single_frame"))]
+    #[test]
+    fn root_and_current_location() {
+        const ORIGIN: ConstLocation = ConstLocation::new("src/db.rs", 40, 9);
+        const SERVICE: ConstLocation = ConstLocation::new("src/service.rs", 12, 5);
+        const API: ConstLocation = ConstLocation::new("src/api.rs", 7, 5);
+
+        let mut err = EzError::message("connection refused");
+        assert_eq!(None, err.root_location());
+        assert_eq!(None, err.current_location());
+
+        err.add_frame(&ORIGIN);
+        err.add_frame(&SERVICE);
+        err.add_frame(&API);
+        assert_eq!(Some(&ORIGIN), err.root_location());
+        assert_eq!(Some(&API), err.current_location());
+    }
+
+    #[test]
+    fn with_code() {
+        let err = EzError::new(ErrorType::IndexOutOfBounds(3, 3));
+        assert_eq!(None, err.code());
+        let err = err.with_code(4001);
+        assert_eq!(Some(4001), err.code());
+        assert_eq!(&ErrorType::IndexOutOfBounds(3, 3), err.ty());
+
+        let custom = EzError::custom(7, "Domain".into(), "msg".into());
+        assert_eq!(Some(7), custom.code());
+        assert_eq!(Some(8), custom.with_code(8).code());
+
+        let merged = EzError::message("outer").with(EzError::message("inner").with_code(9));
+        assert_eq!(Some(9), merged.code());
+    }
+
+    #[test]
+    fn not_found() {
+        let err = EzError::not_found(format!("user {}", 42));
+
+        assert_eq!(
+            &ErrorType::NotFound {
+                resource: "user 42".into()
+            },
+            err.ty()
+        );
+        assert_eq!("NotFound", err.ty().name());
+        assert_eq!("user 42 not found", err.ty().clone().format());
+        assert_eq!("not_found", err.metric_label());
+        assert_eq!(404, err.http_status());
+        assert!(err
+            .render()
+            .starts_with("Error NotFound: user 42 not found\n"));
+    }
+
+    #[cfg(not(feature = "single_frame"))]
+    #[test]
+    fn into_components() {
+        const ORIGIN: ConstLocation = ConstLocation::new("src/db.rs", 40, 9);
+        const API: ConstLocation = ConstLocation::new("src/api.rs", 7, 5);
+
+        let mut err = EzError::new(ErrorType::KeyNotFound("id".into())).context("loading user");
+        err.add_frame(&ORIGIN);
+        err.add_frame(&API);
+
+        let (ty, frames, contexts) = err.clone().into_components();
+        assert_eq!(ErrorType::KeyNotFound("id".into()), ty);
+        assert_eq!(vec![&ORIGIN, &API], frames);
+        assert_eq!(vec!["loading user".to_owned()], contexts);
+
+        let mut rebuilt = EzError::new(ty);
+        for frame in frames {
+            rebuilt.add_frame(frame);
+        }
+        for context in &contexts {
+            rebuilt = rebuilt.context(context);
+        }
+        assert_eq!(err, rebuilt);
+    }
+
+    #[test]
+    fn eq_kind() {
+        let a = Err::<(), _>(EzError::new(ErrorType::IndexOutOfBounds(3, 3)))
+            .loc(flc!())
+            .unwrap_err();
+        let b = Err::<(), _>(EzError::new(ErrorType::IndexOutOfBounds(3, 3)))
+            .loc(flc!())
+            .unwrap_err()
+            .context("while reading row 3");
+        assert!(a.eq_kind(&b));
+        assert_ne!(a, b);
+
+        let c = EzError::new(ErrorType::IndexOutOfBounds(4, 3));
+        assert!(!a.eq_kind(&c));
+    }
+
+    #[test]
+    fn split_parts() {
+        let err = EzError::new(ErrorType::Multiple(vec![
+            EzError::message("name is empty"),
+            EzError::new(ErrorType::NoneOption),
+            EzError::message("age is negative"),
+        ]));
+
+        let names: Vec<&str> = err.parts_iter().map(|e| e.ty().name()).collect();
+        assert_eq!(vec!["Message", "NoneOption", "Message"], names);
+
+        let parts = err.into_parts_vec();
+        assert_eq!(3, parts.len());
+        assert_eq!(&ErrorType::Message("age is negative".into()), parts[2].ty());
+
+        let single = EzError::message("single");
+        assert_eq!(1, single.parts_iter().count());
+        assert_eq!(vec![EzError::message("single")], single.into_parts_vec());
+    }
+
+    #[cfg(not(feature = "single_frame"))]
+    #[cfg(feature = "std")]
+    #[test]
+    fn context_if() {
+        let is_conversion = |ty: &ErrorType| matches!(ty, ErrorType::Conversion { .. });
+
+        let err = "x"
+            .parse::<i32>()
+            .loc(flc!())
+            .context_if(is_conversion, "parsing the port", flc!())
+            .unwrap_err();
+        assert_eq!(&["parsing the port".to_owned()], err.contexts());
+        assert_eq!(2, err.frames().len());
+
+        let err = Err::<(), _>(EzError::message("invalid port"))
+            .context_if(is_conversion, "parsing the port", flc!())
+            .unwrap_err();
+        assert!(err.contexts().is_empty());
+        assert_eq!(1, err.frames().len());
+    }
+
+    #[test]
+    fn display() {
+        let err = Err::<(), _>(EzError::message("deferred"))
+            .loc(flc!())
+            .unwrap_err();
+
+        let text = format!("{}", err);
+        assert_eq!(err.render(), text);
+        assert!(text.starts_with("Error Message: deferred\n\nStacktrace:\n"));
+        let frame = err.frames()[0];
+        assert!(text.ends_with(&format!(
+            "{}:{}:{} (ez_err::core::tests::display)\n",
+            frame.file, frame.line, frame.column
+        )));
+    }
+
+    #[cfg(not(feature = "single_frame"))]
+    #[test]
+    fn ctx() {
+        fn inner() -> Result<()> {
+            bail!("no connection")
+        }
+
+        let res = inner().ctx("syncing", flc!()).ctx("starting up", flc!());
+        let err = res.unwrap_err();
+        assert_eq!(
+            &["syncing".to_owned(), "starting up".to_owned()],
+            err.contexts()
+        );
+        assert_eq!(3, err.frames().len());
+        assert!(err.render().contains("Context:\nsyncing\nstarting up\n\n"));
+    }
+
+    #[test]
+    fn function_name() {
+        fn load() -> &'static ConstLocation {
+            flc!()
+        }
+
+        assert_eq!(
+            Some("ez_err::core::tests::function_name::load"),
+            load().function()
+        );
+        let in_closure = || flc!();
+        assert_eq!(
+            Some("ez_err::core::tests::function_name"),
+            in_closure().function()
+        );
+        assert_eq!(None, ConstLocation::new("src/x.rs", 1, 1).function());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn io_kind() {
+        let err = std::fs::File::open("/does/not/exist")
+            .loc(flc!())
+            .unwrap_err();
+
+        match err.ty() {
+            ErrorType::Io(kind, _) => assert_eq!(std::io::ErrorKind::NotFound, *kind),
+            ty => panic!("unexpected error type {:?}", ty),
+        }
+        assert_eq!("Io", err.ty().name());
+        assert_eq!("io", err.metric_label());
+
+        let err = "x".parse::<u8>().loc(flc!()).unwrap_err();
+        assert_eq!("Conversion", err.ty().name());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn conversion_source_type() {
+        let err = "x".parse::<u8>().loc(flc!()).unwrap_err();
+
+        match err.ty() {
+            ErrorType::Conversion {
+                source_type,
+                message,
+            } => {
+                assert!(source_type.ends_with("ParseIntError"));
+                assert_eq!("invalid digit found in string", message);
+            }
+            ty => panic!("unexpected error type {:?}", ty),
+        }
+        assert!(err.is_conversion());
+        let display = err.ty().to_string();
+        assert!(display.starts_with("invalid digit found in string (from "));
+        assert!(display.ends_with("ParseIntError)"));
+        assert_eq!("invalid digit found in string", err.ty().clone().format());
+        assert!(!EzError::new(ErrorType::Internal("explicit".into())).is_conversion());
+    }
+
+    #[cfg(not(feature = "single_frame"))]
+    #[test]
+    fn clone() {
+        let mut err = EzError::message("shared").context("while testing");
+        err.add_frame(flc!());
+        err.add_boundary("worker");
+        err.add_tagged_frame(flc!(), 3);
+
+        let copy = err.clone();
+        assert_eq!(err, copy);
+        assert_eq!(2, copy.frames().len());
+        assert_eq!(err.render(), copy.render());
+    }
+
+    #[test]
+    fn custom_code() {
+        let err = EzError::custom(42, "LoadError".into(), "no config".into());
+        assert_eq!(Some(42), err.code());
+        assert_eq!(Some(42), err.ty().code());
+
+        let err = EzError::new(ErrorType::NoneOption);
+        assert_eq!(None, err.code());
+        assert_eq!(None, EzError::message("plain").code());
+    }
+
+    #[cfg(not(feature = "single_frame"))]
+    #[test]
+    fn loc_does_not_rewrap() {
+        let mut res: Result<()> = Err(EzError::message("original"));
+        for expected in 1..=5 {
+            res = res.loc(flc!());
+            let err = res.as_ref().unwrap_err();
+            assert_eq!(expected, err.frames().len());
+            assert_eq!(&ErrorType::Message("original".into()), err.ty());
+        }
+    }
+
+    #[test]
+    fn from_panic_payloads() {
+        let err = EzError::from_panic(Box::new("static message"));
+        assert_eq!(&ErrorType::Panic("static message".into()), err.ty());
+
+        let err = EzError::from_panic(Box::new(String::from("owned message")));
+        assert_eq!(&ErrorType::Panic("owned message".into()), err.ty());
+        assert_eq!(12, err.numeric_code());
+
+        let err = EzError::from_panic(Box::new(42));
+        assert_eq!(&ErrorType::Panic("unknown panic".into()), err.ty());
+        assert_eq!("Panic", err.ty().name());
+    }
+
+    #[cfg(not(any(feature = "single_frame", feature = "timestamps")))]
+    #[test]
+    fn dedup_recursive_frames() {
+        fn recurse(depth: u32) -> Result<()> {
+            if depth == 0 {
+                return Err(EzError::message("bottom")).loc(flc!());
+            }
+            recurse(depth - 1).loc(flc!())
+        }
+
+        let mut err = recurse(42).unwrap_err();
+        assert_eq!(43, err.frames().len());
+
+        err.dedup_consecutive();
+        let frames = err.frames();
+        assert_eq!(2, frames.len());
+        let rec = frames[1];
+        let collapsed = format!(
+            "{}:{}:{} ({}) (x42)\n",
+            rec.file,
+            rec.line,
+            rec.column,
+            rec.function().unwrap()
+        );
+        assert!(err.render().ends_with(&collapsed));
+        assert_eq!(42, err.unique_frames()[1].1);
+
+        // Deduplicating again after more frames were added keeps counting.
+        err.add_frame(rec);
+        err.dedup_consecutive();
+        assert_eq!(2, err.frames().len());
+        assert!(err.render().ends_with("(x43)\n"));
+    }
+
+    #[cfg(not(any(feature = "single_frame", feature = "timestamps")))]
+    #[test]
+    fn dedup_keeps_tags_and_boundaries() {
+        const LOOP: ConstLocation = ConstLocation::new("src/loop.rs", 3, 9);
+
+        let mut err = EzError::message("looped");
+        err.add_frame(&LOOP);
+        err.add_frame(&LOOP);
+        err.add_tagged_frame(&LOOP, 7);
+        err.add_frame(&LOOP);
+        err.add_boundary("worker");
+        err.add_frame(&LOOP);
+        err.add_frame(&LOOP);
+        err.dedup_consecutive();
+
+        assert_eq!(
+            "src/loop.rs:3:9 (x2)\nsrc/loop.rs:3:9 #7\nsrc/loop.rs:3:9\n--- worker ---\nsrc/loop.rs:3:9 (x2)\n",
+            err.trace_string()
+        );
+    }
+
+    #[cfg(not(any(feature = "single_frame", feature = "timestamps")))]
+    #[test]
+    fn prepend_frame() {
+        const FIRST: ConstLocation = ConstLocation::new("src/first.rs", 1, 1);
+        const SECOND: ConstLocation = ConstLocation::new("src/second.rs", 2, 1);
+        const THIRD: ConstLocation = ConstLocation::new("src/third.rs", 3, 1);
+        const FOURTH: ConstLocation = ConstLocation::new("src/fourth.rs", 4, 1);
+
+        let mut err = EzError::message("merged");
+        err.add_tagged_frame(&THIRD, 7);
+        err.prepend_frame(&SECOND);
+        err.add_frame(&FOURTH);
+        err.prepend_frame(&FIRST);
+
+        assert_eq!(&[&FIRST, &SECOND, &THIRD, &FOURTH], err.frames());
+        assert_eq!(
+            "src/first.rs:1:1\nsrc/second.rs:2:1\nsrc/third.rs:3:1 #7\nsrc/fourth.rs:4:1\n",
+            err.trace_string()
+        );
+    }
+
+    #[test]
+    fn loc_map_chooses_type() {
+        let res: core::result::Result<(), u32> = Err(404);
+        let err = res
+            .loc_map(
+                |code| ErrorType::IndexOutOfBounds(code as usize, 10),
+                flc!(),
+            )
+            .unwrap_err();
+
+        assert_eq!(&ErrorType::IndexOutOfBounds(404, 10), err.ty());
+        assert_eq!(1, err.frames().len());
+        assert_eq!(
+            Ok(3),
+            Ok::<_, u32>(3).loc_map(|_| ErrorType::NoneOption, flc!())
+        );
+    }
+
+    #[test]
+    fn builder_matches_manual() {
+        const INNER: ConstLocation = ConstLocation::new("src/inner.rs", 3, 9);
+        const OUTER: ConstLocation = ConstLocation::new("src/outer.rs", 12, 5);
+
+        let built = EzError::builder()
+            .ty(ErrorType::EmptySlice)
+            .frame(&INNER)
+            .frame(&OUTER)
+            .build();
+
+        let mut manual = EzError::new(ErrorType::EmptySlice);
+        manual.add_frame(&INNER);
+        manual.add_frame(&OUTER);
+
+        assert_eq!(manual, built);
+        assert_eq!(
+            EzError::message("failed"),
+            EzError::builder().message("failed").build()
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn log_err_keeps_result() {
+        let res: Result<i32> = Err(EzError::message("logged")).loc(flc!());
+        let expected = res.clone();
+
+        assert_eq!(expected, res.log_err());
+        assert_eq!(Ok(3), Ok(3).log_err());
+    }
+
+    #[test]
+    fn aggregate_renders_parts() {
+        const FIRST: ConstLocation = ConstLocation::new("src/first.rs", 3, 9);
+        const SECOND: ConstLocation = ConstLocation::new("src/second.rs", 12, 5);
+
+        let results = vec![
+            Ok(1),
+            Err(EzError::message("bad input")).loc(&FIRST),
+            Err(EzError::new(ErrorType::EmptySlice)).loc(&SECOND),
+        ];
+        let err = results.into_iter().collect_errors().unwrap_err();
+
+        assert_eq!(
+            "2 errors occurred\n- Message: bad input\n- EmptySlice: slice was empty",
+            err.ty().clone().format()
+        );
+        assert!(err.render().ends_with(
+            "Stacktrace:\nPart 1 (Message):\n  src/first.rs:3:9\nPart 2 (EmptySlice):\n  src/second.rs:12:5\n"
+        ));
+        assert_eq!(
+            Ok(vec![1, 2]),
+            vec![Ok(1), Ok(2)].into_iter().collect_errors()
+        );
+    }
+
+    #[test]
+    fn with_keeps_both_errors() {
+        const INNER: ConstLocation = ConstLocation::new("src/inner.rs", 3, 9);
+        const OUTER: ConstLocation = ConstLocation::new("src/outer.rs", 12, 5);
+
+        let mut first = EzError::new(ErrorType::IndexOutOfBounds(5, 2)).context("reading row");
+        first.add_frame(&INNER);
+        let mut second = EzError::message("invalid header")
+            .context("reading row")
+            .context("parsing file")
+            .because("missing column");
+        second.add_frame(&OUTER);
+
+        let err = first.clone().with(second.clone());
+        assert_eq!(&ErrorType::Multiple(vec![first, second]), err.ty());
+        let frames: Vec<_> = err.parts_iter().map(|part| part.frames()).collect();
+        assert_eq!(vec![&[&INNER][..], &[&OUTER][..]], frames);
+        assert!(err.frames().is_empty());
+        assert_eq!(
+            &["reading row".to_owned(), "parsing file".to_owned()],
+            err.contexts()
+        );
+        assert_eq!(&["missing column".to_owned()], err.causes());
+
+        let err = err.with(EzError::new(ErrorType::EmptySlice));
+        assert_eq!(3, err.parts_iter().count());
+    }
+
+    #[test]
+    fn with_same_type() {
+        let err = EzError::new(ErrorType::EmptySlice).with(EzError::new(ErrorType::EmptySlice));
+        assert_eq!(&ErrorType::EmptySlice, err.ty());
+    }
+
+    #[cfg(not(any(feature = "single_frame", feature = "timestamps")))]
+    #[test]
+    fn loc_note_in_trace() {
+        const LOOP: ConstLocation = ConstLocation::new("src/loop.rs", 3, 9);
+
+        let err = Err::<(), _>(EzError::message("denied"))
+            .loc_note(&LOOP, "reading the header")
+            .loc_tagged(&LOOP, 2)
+            .unwrap_err();
+        assert_eq!(
+            "src/loop.rs:3:9 - reading the header\nsrc/loop.rs:3:9 #2\n",
+            err.trace_string()
+        );
+
+        let err = None::<u32>
+            .loc_note(&LOOP, "looking up the id")
+            .unwrap_err();
+        assert_eq!("src/loop.rs:3:9 - looking up the id\n", err.trace_string());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn type_predicates() {
+        type Predicate = fn(&EzError) -> bool;
+        let predicates: [(Predicate, ErrorType); 20] = [
+            (EzError::is_internal, ErrorType::Internal("x".into())),
+            (EzError::is_none_option, ErrorType::NoneOption),
+            (EzError::is_index_oob, ErrorType::IndexOutOfBounds(3, 2)),
+            (EzError::is_range_oob, ErrorType::RangeOutOfBounds(0, 3, 2)),
+            (EzError::is_invalid_range, ErrorType::InvalidRange),
+            (EzError::is_empty_slice, ErrorType::EmptySlice),
+            (EzError::is_shape_mismatch, ErrorType::ShapeMismatch(1, 2)),
+            (
+                EzError::is_dimension_oob,
+                ErrorType::DimensionOutOfBounds(0, 3, 2),
+            ),
+            (
+                EzError::is_io,
+                ErrorType::Io(std::io::ErrorKind::NotFound, "io".into()),
+            ),
+            (
+                EzError::is_key_not_found,
+                ErrorType::KeyNotFound("k".into()),
+            ),
+            (EzError::is_panic, ErrorType::Panic("boom".into())),
+            (
+                EzError::is_timeout,
+                ErrorType::Timeout(Duration::from_secs(1)),
+            ),
+            (
+                EzError::is_parse,
+                ErrorType::Parse {
+                    input: "x".into(),
+                    target: "u32",
+                },
+            ),
+            (
+                EzError::is_conversion,
+                ErrorType::Conversion {
+                    source_type: "Foreign",
+                    message: "msg".into(),
+                },
+            ),
+            (EzError::is_div_by_zero, ErrorType::DivByZero),
+            (
+                EzError::is_arithmetic_overflow,
+                ErrorType::ArithmeticOverflow,
+            ),
+            (
+                EzError::is_not_found,
+                ErrorType::NotFound {
+                    resource: "user 1".into(),
+                },
+            ),
+            (EzError::is_multiple, ErrorType::Multiple(Vec::new())),
+            (EzError::is_message, ErrorType::Message("msg".into())),
+            (
+                EzError::is_custom,
+                ErrorType::Custom {
+                    code: 1,
+                    name: "Domain".into(),
+                    message: "msg".into(),
+                },
+            ),
+        ];
+
+        for (i, (predicate, _)) in predicates.iter().enumerate() {
+            for (j, (_, ty)) in predicates.iter().enumerate() {
+                let err = EzError::new(ty.clone());
+                assert_eq!(i == j, predicate(&err), "{} on {:?}", i, ty);
+            }
+        }
+
+        let err = EzError::new(ErrorType::NoneOption);
+        assert!(err.matches_name("NoneOption"));
+        assert!(!err.matches_name("Message"));
+    }
+
+    /// Only implements the required method of [`LocData`].
+    struct OnlyLoc(Option<u32>);
+
+    impl LocData<u32> for OnlyLoc {
+        type Result = Result<u32>;
+
+        fn loc(self, flc: &'static ConstLocation) -> Self::Result {
+            self.0.loc(flc)
+        }
+    }
+
+    #[test]
+    fn loc_data_defaults() {
+        assert_eq!(Ok(1), OnlyLoc(Some(1)).loc_tagged(flc!(), 7));
+        let err = OnlyLoc(None).loc_tagged(flc!(), 7).unwrap_err();
+        assert_eq!(&ErrorType::NoneOption, err.ty());
+        assert_eq!(1, err.frames().len());
+
+        let err = OnlyLoc(None).loc_note(flc!(), "loading").unwrap_err();
+        assert_eq!(1, err.frames().len());
+        let err = OnlyLoc(None).loc_debug(flc!()).unwrap_err();
+        assert_eq!(1, err.frames().len());
+
+        let unused = || -> ErrorType { panic!("must not be called") };
+        let err = OnlyLoc(None).loc_or_else(unused, flc!()).unwrap_err();
+        assert_eq!(&ErrorType::NoneOption, err.ty());
+    }
+
+    #[cfg(not(feature = "single_frame"))]
+    #[cfg(feature = "std")]
+    #[test]
+    fn loc_or_else_is_lazy() {
+        let expensive = || -> ErrorType { panic!("must not be called") };
+        assert_eq!(Ok(1), Some(1).loc_or_else(expensive, flc!()));
+        assert_eq!(Ok(2), Ok::<_, EzError>(2).loc_or_else(expensive, flc!()));
+        assert_eq!(Ok(3), "3".parse::<u32>().loc_or_else(expensive, flc!()));
+
+        let err = None::<u32>
+            .loc_or_else(|| ErrorType::Message("missing".into()), flc!())
+            .unwrap_err();
+        assert_eq!(&ErrorType::Message("missing".into()), err.ty());
+
+        let err = "x"
+            .parse::<u32>()
+            .loc_or_else(|| ErrorType::InvalidRange, flc!())
+            .unwrap_err();
+        assert_eq!(&ErrorType::InvalidRange, err.ty());
+        assert_eq!(&["invalid digit found in string".to_owned()], err.causes());
+
+        let err = Err::<(), _>(EzError::message("inner").context("loading"))
+            .loc(flc!())
+            .loc_or_else(expensive, flc!())
+            .unwrap_err();
+        assert_eq!(&ErrorType::Message("inner".into()), err.ty());
+        assert_eq!(2, err.frames().len());
+        assert_eq!(&["loading".to_owned()], err.contexts());
+    }
+
+    #[test]
+    fn loc_msg() {
+        let err = None::<u32>.loc_msg("user id missing", flc!()).unwrap_err();
+        assert_eq!(&ErrorType::Message("user id missing".into()), err.ty());
+        assert_eq!(1, err.frames().len());
+
+        assert_eq!(Ok(7), Some(7).loc_msg("user id missing", flc!()));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn loc_debug() {
+        let frames = usize::from(cfg!(debug_assertions));
+
+        let err = Err::<(), _>(EzError::message("denied"))
+            .loc_debug(flc!())
+            .unwrap_err();
+        assert_eq!(frames, err.frames().len());
+
+        let err = None::<u32>.loc_debug(flc!()).unwrap_err();
+        assert_eq!(&ErrorType::NoneOption, err.ty());
+        assert_eq!(frames, err.frames().len());
+
+        let err = "x".parse::<u32>().loc_debug(flc!()).unwrap_err();
+        assert!(err.is_conversion());
+        assert_eq!(frames, err.frames().len());
+
+        assert_eq!(Ok(4), Some(4).loc_debug(flc!()));
+    }
+
+    #[test]
+    fn timeout() {
+        let err = EzError::timeout(Duration::from_millis(1500));
+
+        assert_eq!(&ErrorType::Timeout(Duration::from_millis(1500)), err.ty());
+        assert_eq!("Timeout", err.ty().name());
+        assert_eq!("Operation timed out after 1.5s", err.ty().clone().format());
+        assert_eq!(13, err.numeric_code());
+        assert_eq!(504, err.http_status());
+        assert!(err.is_transient());
+    }
 }
@@ -0,0 +1,218 @@
+//! Dependency-free JSON output of errors.
+
+use crate::alloc_prelude::*;
+use crate::core::*;
+use core::fmt::Write;
+
+impl EzError {
+    /// Converts the error into a single-line JSON object with the `name` and
+    /// `message` of the error, its `context` messages, `causes` and frames.
+    /// Frames include the `function` if it is known.
+    /// The `url` is only included if one was attached with
+    /// [`EzError::with_url`].
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// const LOC: ConstLocation = ConstLocation::new("src/x.rs", 12, 5);
+    /// let mut err = EzError::message("failed").context("loading config");
+    /// err.add_frame(&LOC);
+    ///
+    /// assert_eq!(
+    ///     concat!(
+    ///         r#"{"name":"Message","message":"failed","context":["loading config"],"causes":[],"#,
+    ///         r#""frames":[{"file":"src/x.rs","line":12,"column":5}]}"#
+    ///     ),
+    ///     err.to_json()
+    /// );
+    /// ```
+    pub fn to_json(&self) -> String {
+        let mut s = String::with_capacity(256);
+        s.push_str("{\"name\":");
+        write_json_string(&mut s, self.ty().name());
+        s.push_str(",\"message\":");
+        write_json_string(&mut s, &self.ty().to_string());
+        s.push_str(",\"context\":");
+        write_json_array(&mut s, self.contexts());
+        s.push_str(",\"causes\":");
+        write_json_array(&mut s, self.causes());
+        if let Some(url) = self.url() {
+            s.push_str(",\"url\":");
+            write_json_string(&mut s, url);
+        }
+
+        #[cfg(not(feature = "no_stacktrace"))]
+        {
+            s.push_str(",\"frames\":[");
+            for (idx, frame) in self.frames().iter().enumerate() {
+                if idx > 0 {
+                    s.push(',');
+                }
+                s.push_str("{\"file\":");
+                write_json_string(&mut s, frame.file);
+                let _ = write!(s, ",\"line\":{},\"column\":{}", frame.line, frame.column);
+                if let Some(function) = frame.function() {
+                    s.push_str(",\"function\":");
+                    write_json_string(&mut s, function);
+                }
+                s.push('}');
+            }
+            s.push(']');
+        }
+
+        s.push('}');
+        s
+    }
+
+    /// Converts the error into an RFC 7807 `application/problem+json` object.
+    /// The `title` is the name of the error, the `detail` its message and the
+    /// `status` is taken from [`EzError::http_status`]. The `type` is the URL
+    /// attached with [`EzError::with_url`], or `about:blank` if there is none.
+    /// `instance` identifies the specific occurrence of the problem and is
+    /// omitted if `None`.
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// let err = EzError::custom(404, "UserNotFound".into(), "no user 42".into());
+    /// assert_eq!(
+    ///     r#"{"type":"about:blank","title":"UserNotFound","status":404,"detail":"no user 42"}"#,
+    ///     err.to_problem_json(None)
+    /// );
+    /// ```
+    pub fn to_problem_json(&self, instance: Option<&str>) -> String {
+        let mut s = String::with_capacity(256);
+        s.push_str("{\"type\":");
+        write_json_string(&mut s, self.url().unwrap_or("about:blank"));
+        s.push_str(",\"title\":");
+        write_json_string(&mut s, self.ty().name());
+        let _ = write!(s, ",\"status\":{},\"detail\":", self.http_status());
+        write_json_string(&mut s, &self.ty().to_string());
+        if let Some(instance) = instance {
+            s.push_str(",\"instance\":");
+            write_json_string(&mut s, instance);
+        }
+        s.push('}');
+        s
+    }
+}
+
+/// Writes the value as a quoted and escaped JSON string.
+pub(crate) fn write_json_string(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Writes the values as a JSON array of strings.
+fn write_json_array(out: &mut String, values: &[String]) {
+    out.push('[');
+    for (idx, value) in values.iter().enumerate() {
+        if idx > 0 {
+            out.push(',');
+        }
+        write_json_string(out, value);
+    }
+    out.push(']');
+}
+
+#[cfg(all(test, not(feature = "panic_on_error")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape() {
+        let mut s = String::new();
+        write_json_string(&mut s, "a \"quoted\" \\ line\nwith\u{1}control");
+
+        assert_eq!(r#""a \"quoted\" \\ line\nwith\u0001control""#, s);
+    }
+
+    #[test]
+    fn problem_json_message() {
+        let err = EzError::message("value \"x\" is invalid");
+
+        assert_eq!(
+            r#"{"type":"about:blank","title":"Message","status":500,"detail":"value \"x\" is invalid","instance":"/orders/7"}"#,
+            err.to_problem_json(Some("/orders/7"))
+        );
+    }
+
+    #[test]
+    fn problem_json_custom() {
+        let err = EzError::custom(409, "Conflict".into(), "order exists".into());
+
+        assert_eq!(
+            r#"{"type":"about:blank","title":"Conflict","status":409,"detail":"order exists"}"#,
+            err.to_problem_json(None)
+        );
+    }
+
+    #[test]
+    fn problem_json_url() {
+        let err = EzError::message("disk full").with_url("https://example.com/disk");
+
+        assert_eq!(
+            r#"{"type":"https://example.com/disk","title":"Message","status":500,"detail":"disk full"}"#,
+            err.to_problem_json(None)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn json_lines() {
+        const LOC: ConstLocation = ConstLocation::new("src/x.rs", 12, 5);
+        let mut err = EzError::message("line\nbreak").with_url("https://example.com");
+        err.add_frame(&LOC);
+
+        let mut out = Vec::new();
+        assert_eq!(Some(1), Ok(1).handle_json_to(&mut out));
+        assert!(out.is_empty());
+        assert_eq!(None, Result::<()>::Err(err).handle_json_to(&mut out));
+
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(1, lines.len());
+        assert!(out.ends_with('\n'));
+        assert!(lines[0].starts_with("{\"name\":\"Message\",\"message\":\"line\\nbreak\""));
+        assert!(lines[0].contains(r#""url":"https://example.com""#));
+        assert!(lines[0].ends_with(r#""frames":[{"file":"src/x.rs","line":12,"column":5}]}"#));
+    }
+
+    #[cfg(not(feature = "single_frame"))]
+    #[cfg(feature = "std")]
+    #[test]
+    fn handle_json_fields() {
+        const INNER: ConstLocation = ConstLocation::new("src/inner.rs", 3, 9);
+        const OUTER: ConstLocation = ConstLocation::new("src/outer.rs", 12, 5);
+        let res: Result<()> = Err(EzError::message("disk \"full\"").context("saving"))
+            .loc(&INNER)
+            .loc(&OUTER);
+
+        let json: serde_json::Value =
+            serde_json::from_str(&res.clone().unwrap_err().to_json()).unwrap();
+        assert_eq!("Message", json["name"]);
+        assert_eq!("disk \"full\"", json["message"]);
+        assert_eq!(serde_json::json!(["saving"]), json["context"]);
+        assert_eq!(
+            serde_json::json!([
+                {"file": "src/inner.rs", "line": 3, "column": 9},
+                {"file": "src/outer.rs", "line": 12, "column": 5},
+            ]),
+            json["frames"]
+        );
+
+        assert_eq!(None, res.handle_json());
+        assert_eq!(Some(1), Ok(1).handle_json());
+    }
+}
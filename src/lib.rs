@@ -78,8 +78,18 @@
 //! ```
 //!
 //! # Features
+//! * `anyhow` - convert [`EzError`] into `anyhow::Error`, so `?` works in functions that return `anyhow::Result`. The display output of the converted error includes the stacktrace.
+//! * `color` - print errors with [`handle`] in color when stderr is a terminal: the error name in bold red and the locations in gray. Setting the `NO_COLOR` environment variable disables it again.
+//! * `compact` - enable `CompactError`, a small error type that needs no allocation.
+//! * `std` (default) - enable everything that needs the standard library: printing errors with [`handle`], error scopes, retry helpers, the conversion from [`std::error::Error`] and [`ErrorType::Io`]. Without it the crate is `no_std` and only needs `alloc` (the `alloc` feature).
 //! * `log` - enable compatibility with the [log](https://crates.io/crates/log) crate. The code will by default output to `error!(...)`.
+//! * `tracing` - enable compatibility with the [tracing](https://crates.io/crates/tracing) crate. Errors are emitted with `tracing::error!` and the `error.name`, `error.message` and `error.trace` fields. Takes precedence over `log` if both are enabled.
 //! * `no_stacktrace` - disable any stacktrace collection. This might be useful in a scenario where leaking source information is problematic.
+//! * `panic_on_error` - panic as soon as an [`EzError`] is created instead of returning it. Run with `RUST_BACKTRACE=1` to see where an error comes from. Only meant for debugging, so it only has an effect in builds with debug assertions.
+//! * `single_frame` - only record the first frame of an error (where it was created) and ignore the frames added while it is propagated. This keeps the error small while still pointing to its origin. Has no effect if `no_stacktrace` is enabled, which still disables all frames.
+//! * `std_backtrace` - capture a [`std::backtrace::Backtrace`] when an [`EzError`] is converted from a foreign error and print it after the frames of [`flc!`].
+//! * `timestamps` - record the time at which every frame is added and print the time elapsed since the first frame in the stacktrace.
+//! * `serde` - implement `Serialize` and `Deserialize` for [`EzError`], [`ErrorType`] and [`ConstLocation`].
 //!
 //! # License
 //! This project is licensed under the [MIT license](https://github.com/MariusSoft-LLC/ez-err/blob/main/LICENSE).
@@ -92,12 +102,48 @@
 //! [`Result<T>`]: prelude::Result
 //! [`EzError`]: prelude::EzError
 //! [`ConstLocation`]: prelude::ConstLocation
+//! [`ErrorType`]: prelude::ErrorType
+//! [`ErrorType::Io`]: prelude::ErrorType::Io
+//! [`handle`]: prelude::Handle::handle
 //! [`eget`]: prelude::SliceExt::eget
 //! [`eget_mut`]: prelude::SliceExtMut::eget_mut
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![warn(missing_docs)]
 #![deny(warnings)]
 
+#[cfg(not(feature = "alloc"))]
+compile_error!("ez-err requires the `alloc` feature");
+
+extern crate alloc;
+
+mod alloc_prelude;
+#[cfg(feature = "anyhow")]
+mod anyhow_impl;
+pub mod checked_ext;
+#[cfg(feature = "compact")]
+pub mod compact;
 pub mod core;
+pub mod json;
+pub mod map_ext;
+pub mod parse_ext;
 pub mod prelude;
+pub mod render;
+#[cfg(feature = "std")]
+pub mod retry;
+#[cfg(feature = "std")]
+pub mod ring_log;
+#[cfg(feature = "std")]
+pub mod scope;
+#[cfg(feature = "serde")]
+mod serde_impl;
 pub mod slice_ext;
+#[cfg(feature = "std")]
+pub mod termination;
+
+/// Items used by the exported macros, which have to work in `no_std` crates.
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::format;
+    pub use alloc::string::String;
+}
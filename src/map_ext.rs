@@ -0,0 +1,154 @@
+//! Map extensions that integrate well with the error handling system.
+
+use crate::alloc_prelude::*;
+use crate::core::*;
+use crate::flc;
+use alloc::collections::BTreeMap;
+use core::borrow::Borrow;
+use core::fmt::Debug;
+#[cfg(feature = "std")]
+use core::hash::{BuildHasher, Hash};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+/// Extension trait for maps.
+pub trait MapExt<Q, V>
+where
+    Q: ?Sized,
+{
+    /// Returns a reference to the value of the key or [`Err(_)`] with
+    /// [`ErrorType::KeyNotFound`] if the map does not contain the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// # use std::collections::HashMap;
+    /// # #[cfg(feature = "std")] {
+    /// let mut ports = HashMap::new();
+    /// ports.insert("http".to_owned(), 80);
+    ///
+    /// assert_eq!(Ok(&80), ports.eget("http"));
+    /// assert_eq!(
+    ///     &ErrorType::KeyNotFound("\"ftp\"".into()),
+    ///     ports.eget("ftp").err().unwrap().ty()
+    /// );
+    /// # }
+    /// ```
+    fn eget(&self, key: &Q) -> Result<&V>;
+}
+
+/// Mutable version of [`MapExt`].
+pub trait MapExtMut<Q, V>
+where
+    Q: ?Sized,
+{
+    /// Returns a mutable reference to the value of the key or [`Err(_)`] if
+    /// the map does not contain the key (see [`eget`]).
+    ///
+    /// [`eget`]: MapExt::eget
+    fn eget_mut(&mut self, key: &Q) -> Result<&mut V>;
+}
+
+/// Creates the error for a missing key.
+fn key_not_found<Q: Debug + ?Sized>(key: &Q) -> EzError {
+    EzError::new(ErrorType::KeyNotFound(format!("{:?}", key)))
+}
+
+#[cfg(feature = "std")]
+impl<K, V, Q, S> MapExt<Q, V> for HashMap<K, V, S>
+where
+    K: Borrow<Q> + Hash + Eq,
+    Q: Hash + Eq + Debug + ?Sized,
+    S: BuildHasher,
+{
+    #[inline]
+    fn eget(&self, key: &Q) -> Result<&V> {
+        match self.get(key) {
+            Some(v) => Ok(v),
+            None => Err(key_not_found(key)).loc(flc!()),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, Q, S> MapExtMut<Q, V> for HashMap<K, V, S>
+where
+    K: Borrow<Q> + Hash + Eq,
+    Q: Hash + Eq + Debug + ?Sized,
+    S: BuildHasher,
+{
+    #[inline]
+    fn eget_mut(&mut self, key: &Q) -> Result<&mut V> {
+        match self.get_mut(key) {
+            Some(v) => Ok(v),
+            None => Err(key_not_found(key)).loc(flc!()),
+        }
+    }
+}
+
+impl<K, V, Q> MapExt<Q, V> for BTreeMap<K, V>
+where
+    K: Borrow<Q> + Ord,
+    Q: Ord + Debug + ?Sized,
+{
+    #[inline]
+    fn eget(&self, key: &Q) -> Result<&V> {
+        match self.get(key) {
+            Some(v) => Ok(v),
+            None => Err(key_not_found(key)).loc(flc!()),
+        }
+    }
+}
+
+impl<K, V, Q> MapExtMut<Q, V> for BTreeMap<K, V>
+where
+    K: Borrow<Q> + Ord,
+    Q: Ord + Debug + ?Sized,
+{
+    #[inline]
+    fn eget_mut(&mut self, key: &Q) -> Result<&mut V> {
+        match self.get_mut(key) {
+            Some(v) => Ok(v),
+            None => Err(key_not_found(key)).loc(flc!()),
+        }
+    }
+}
+
+#[cfg(all(test, not(feature = "panic_on_error")))]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn hash_map() {
+        let mut map = HashMap::new();
+        map.insert(1, "one");
+
+        assert_eq!(Ok(&"one"), map.eget(&1));
+        *map.eget_mut(&1).unwrap() = "uno";
+        assert_eq!(Some(&"uno"), map.get(&1));
+
+        let err = map.eget(&2).err().unwrap();
+        assert_eq!(&ErrorType::KeyNotFound("2".into()), err.ty());
+        assert_eq!(1, err.frames().len());
+        assert_eq!(file!(), err.frames()[0].file);
+
+        let err = map.eget_mut(&3).err().unwrap();
+        assert_eq!(&ErrorType::KeyNotFound("3".into()), err.ty());
+    }
+
+    #[test]
+    fn btree_map() {
+        let mut map = BTreeMap::new();
+        map.insert("a".to_owned(), 1);
+
+        assert_eq!(Ok(&1), map.eget("a"));
+        assert_eq!(Ok(&mut 1), map.eget_mut("a"));
+
+        let err = map.eget("b").err().unwrap();
+        assert_eq!(&ErrorType::KeyNotFound("\"b\"".into()), err.ty());
+        assert_eq!("Key \"b\" was not found", err.ty().to_string());
+        assert_eq!(1, err.frames().len());
+    }
+}
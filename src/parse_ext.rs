@@ -0,0 +1,67 @@
+//! String extensions that integrate well with the error handling system.
+
+use crate::alloc_prelude::*;
+use crate::core::*;
+use crate::flc;
+use core::str::FromStr;
+
+/// Extension trait for strings.
+pub trait ParseExt {
+    /// Parses the string into `T` like [`str::parse`], or returns
+    /// [`ErrorType::Parse`] with the string and the name of `T` if that fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// assert_eq!(Ok(8080), "8080".eparse::<u16>());
+    /// assert_eq!(
+    ///     &ErrorType::Parse {
+    ///         input: "http".to_owned(),
+    ///         target: "u16",
+    ///     },
+    ///     "http".eparse::<u16>().unwrap_err().ty()
+    /// );
+    /// ```
+    fn eparse<T: FromStr>(&self) -> Result<T>;
+}
+
+impl ParseExt for str {
+    #[inline]
+    fn eparse<T: FromStr>(&self) -> Result<T> {
+        match self.parse() {
+            Ok(value) => Ok(value),
+            Err(_) => Err(EzError::new(ErrorType::Parse {
+                input: self.to_owned(),
+                target: core::any::type_name::<T>(),
+            }))
+            .loc(flc!()),
+        }
+    }
+}
+
+#[cfg(all(test, not(feature = "panic_on_error")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_valid() {
+        assert_eq!(Ok(-12), "-12".eparse::<i32>());
+        assert_eq!(Ok(42), String::from("42").eparse::<u8>());
+    }
+
+    #[test]
+    fn parse_invalid() {
+        let err = "300".eparse::<u8>().unwrap_err();
+
+        match err.ty() {
+            ErrorType::Parse { input, target } => {
+                assert_eq!("300", input);
+                assert_eq!("u8", *target);
+            }
+            ty => panic!("unexpected error type {:?}", ty),
+        }
+        assert_eq!("Could not parse \"300\" as u8", err.ty().to_string());
+        assert_eq!(1, err.frames().len());
+    }
+}
@@ -1,6 +1,50 @@
 //! A common set of types needed for a meaningful use of ez-err.
+//!
+//! A single glob import brings in the types, the extension traits, the free
+//! functions like [`handle`] and all macros:
+//!
+//! ```
+//! use ez_err::prelude::*;
+//! # #[cfg(feature = "std")] {
+//!
+//! fn parse_port(port: &str) -> Result<u16> {
+//!     ensure!(!port.is_empty(), "no port given");
+//!     let port = port.parse::<u16>().loc(flc!())?;
+//!     if port == 0 {
+//!         bail!("port {} is reserved", port);
+//!     }
+//!
+//!     Ok(port)
+//! }
+//!
+//! assert_eq!(Some(8080), handle(|| parse_port("8080")));
+//! assert_eq!(None, handle(|| parse_port("0")));
+//! # }
+//! ```
 
 pub use crate::bail;
+pub use crate::bail_custom;
+pub use crate::checked_ext::*;
+#[cfg(feature = "compact")]
+pub use crate::compact::*;
 pub use crate::core::*;
+pub use crate::ensure;
+#[cfg(feature = "std")]
+pub use crate::error_scope;
+pub use crate::etry;
 pub use crate::flc;
+#[cfg(feature = "std")]
+pub use crate::local_frames_only;
+pub use crate::map_ext::*;
+pub use crate::parse_ext::*;
+pub use crate::render::*;
+#[cfg(feature = "std")]
+pub use crate::retry::*;
+#[cfg(feature = "std")]
+pub use crate::ring_log::*;
+#[cfg(feature = "std")]
+pub use crate::scope::*;
 pub use crate::slice_ext::*;
+#[cfg(feature = "std")]
+pub use crate::termination::*;
+pub use crate::zip_eget;
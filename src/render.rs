@@ -0,0 +1,744 @@
+//! Rendering of errors into human-readable text.
+
+use crate::alloc_prelude::*;
+use crate::core::*;
+#[cfg(feature = "std")]
+use crate::ring_log::RingLog;
+use core::fmt::Write;
+#[cfg(feature = "std")]
+use std::io::IsTerminal;
+#[cfg(feature = "std")]
+use std::sync::Arc;
+
+/// Options that control how an [`EzError`] is rendered by
+/// [`EzError::render_with`] and [`Handle::handle_with_options`].
+///
+/// New options may be added in the future, so the struct can't be built with
+/// a struct literal outside of this crate. Start with
+/// [`HandleOptions::default`] and set the fields that should differ.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct HandleOptions {
+    /// Prints the column of every frame as `0`. The `file:line:column` shape
+    /// of the trace is kept, but the output no longer changes when code is
+    /// reformatted, which is useful for snapshot tests.
+    pub normalize_columns: bool,
+
+    /// Renders every frame as an OSC 8 hyperlink to the `file://` URL of the
+    /// source file, which makes frames clickable in supporting terminals.
+    /// Relative paths are resolved against the current directory if the `std`
+    /// feature is enabled. When the error is printed to the console, this
+    /// falls back to plain text if stderr is not a terminal or the
+    /// `NO_HYPERLINKS` environment variable is set.
+    pub hyperlinks: bool,
+
+    /// Colors the output with ANSI escape codes: the name of the error is
+    /// printed in bold red and the location of every frame in gray. With the
+    /// `color` feature this is enabled automatically when the error is
+    /// printed to the console. Like [`HandleOptions::hyperlinks`], it falls
+    /// back to plain text if stderr is not a terminal or the `NO_COLOR`
+    /// environment variable is set.
+    pub color: bool,
+
+    /// Groups consecutive frames of the same module under an `in module:`
+    /// header and indents them. Frames without a module (see
+    /// [`ConstLocation::module`]) are printed without a header.
+    pub group_by_module: bool,
+
+    /// Groups consecutive frames of the same file under a `file:` header and
+    /// prints only `line:column` for each of them. Takes precedence over
+    /// [`HandleOptions::group_by_module`].
+    pub group_by_file: bool,
+
+    /// Additionally stores the output of every handled or reported error in
+    /// the given [`RingLog`]. Only available with the `std` feature.
+    #[cfg(feature = "std")]
+    pub ring_log: Option<Arc<RingLog>>,
+}
+
+impl HandleOptions {
+    /// Returns the options that should be used when printing to the console,
+    /// disabling features the console does not support.
+    #[cfg(feature = "std")]
+    pub(crate) fn for_console(&self) -> HandleOptions {
+        let mut opts = self.clone();
+        if opts.hyperlinks {
+            opts.hyperlinks =
+                std::io::stderr().is_terminal() && std::env::var_os("NO_HYPERLINKS").is_none();
+        }
+        if opts.color || cfg!(feature = "color") {
+            opts.color = std::io::stderr().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+        }
+        opts
+    }
+}
+
+impl EzError {
+    /// Renders the error into the text that is printed by [`Handle::handle`].
+    pub fn render(&self) -> String {
+        self.render_with(&HandleOptions::default())
+    }
+
+    /// Renders the error into text using the given [`HandleOptions`].
+    pub fn render_with(&self, opts: &HandleOptions) -> String {
+        let mut s = String::with_capacity(1024);
+        s.push_str("Error ");
+        let _ = write_styled(&mut s, opts, BOLD_RED, format_args!("{}", self.ty().name()));
+        let _ = writeln!(s, ": {}", self.ty());
+        if let Some(input) = self.input() {
+            let _ = writeln!(s, "input: {}", input);
+        }
+        for cause in self.causes() {
+            let _ = writeln!(s, "Caused by: {}", cause);
+        }
+        if let Some(url) = self.url() {
+            let _ = writeln!(s, "see: {}", url);
+        }
+        s.push('\n');
+
+        if !self.contexts().is_empty() {
+            s.push_str("Context:\n");
+            for context in self.contexts() {
+                s.push_str(context);
+                s.push('\n');
+            }
+            s.push('\n');
+        }
+
+        #[cfg(not(feature = "no_stacktrace"))]
+        {
+            s.push_str("Stacktrace:\n");
+            let _ = self.write_frames(&mut s, opts);
+
+            // The traces of aggregated errors are printed indented below.
+            if let ErrorType::Multiple(errors) = self.ty() {
+                for (idx, part) in errors.iter().enumerate() {
+                    let _ = writeln!(s, "Part {} ({}):", idx + 1, part.ty().name());
+                    let mut trace = String::new();
+                    let _ = part.write_frames(&mut trace, opts);
+                    for line in trace.lines() {
+                        let _ = writeln!(s, "  {}", line);
+                    }
+                }
+            }
+        }
+        #[cfg(feature = "std_backtrace")]
+        if let Some(backtrace) = self.backtrace() {
+            let _ = write!(s, "\nOS backtrace:\n{}", backtrace);
+        }
+        #[cfg(feature = "no_stacktrace")]
+        let _ = opts;
+
+        s
+    }
+
+    /// Returns the stacktrace of the error as it is printed by
+    /// [`Handle::handle`]: one `file:line:column` line per frame, without
+    /// the message of the error. Returns an empty string if the
+    /// `no_stacktrace` feature is enabled.
+    pub fn trace_string(&self) -> String {
+        let mut s = String::new();
+        let _ = self.write_trace(&mut s);
+        s
+    }
+
+    /// Returns the stacktrace like [`EzError::trace_string`], but groups
+    /// consecutive frames of the same file under a `file:` header, see
+    /// [`HandleOptions::group_by_file`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// const QUERY: ConstLocation = ConstLocation::new("src/db.rs", 10, 5);
+    /// const CONNECT: ConstLocation = ConstLocation::new("src/db.rs", 42, 9);
+    /// const HANDLER: ConstLocation = ConstLocation::new("src/api.rs", 7, 13);
+    ///
+    /// let mut err = EzError::message("boom");
+    /// err.add_frame(&QUERY);
+    /// err.add_frame(&CONNECT);
+    /// err.add_frame(&HANDLER);
+    ///
+    /// # #[cfg(not(any(feature = "single_frame", feature = "timestamps")))]
+    /// assert_eq!(
+    ///     "src/db.rs:\n  10:5\n  42:9\nsrc/api.rs:\n  7:13\n",
+    ///     err.trace_string_grouped()
+    /// );
+    /// ```
+    pub fn trace_string_grouped(&self) -> String {
+        #[cfg(not(feature = "no_stacktrace"))]
+        {
+            let opts = HandleOptions {
+                group_by_file: true,
+                ..HandleOptions::default()
+            };
+            let mut s = String::new();
+            let _ = self.write_frames(&mut s, &opts);
+            s
+        }
+        #[cfg(feature = "no_stacktrace")]
+        String::new()
+    }
+
+    /// Writes the stacktrace of the error like [`EzError::trace_string`]
+    /// directly into the writer, e.g. an existing `String` or a
+    /// [`core::fmt::Formatter`], without allocating an intermediate string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// let res: Result<()> = Err(EzError::message("denied")).loc(flc!());
+    /// let err = res.unwrap_err();
+    ///
+    /// let mut s = String::from("trace:\n");
+    /// err.write_trace(&mut s).unwrap();
+    /// assert_eq!(format!("trace:\n{}", err.trace_string()), s);
+    /// ```
+    pub fn write_trace<W: Write + ?Sized>(&self, w: &mut W) -> core::fmt::Result {
+        #[cfg(not(feature = "no_stacktrace"))]
+        {
+            self.write_frames(w, &HandleOptions::default())
+        }
+        #[cfg(feature = "no_stacktrace")]
+        {
+            let _ = w;
+            Ok(())
+        }
+    }
+
+    /// Writes one line per frame together with the markers of the frames.
+    #[cfg(not(feature = "no_stacktrace"))]
+    fn write_frames<W: Write + ?Sized>(
+        &self,
+        w: &mut W,
+        opts: &HandleOptions,
+    ) -> core::fmt::Result {
+        let mut markers = self.markers().iter().peekable();
+        let mut module = None;
+        let mut file = None;
+        #[cfg(feature = "timestamps")]
+        let times = Some(self.frame_times()).filter(|t| t.len() == self.frames().len());
+        for (idx, frame) in self.frames().iter().enumerate() {
+            let mut tag = None;
+            let mut repeat = None;
+            let mut note = None;
+            while let Some((_, marker)) = markers.next_if(|(i, _)| *i == idx) {
+                match marker {
+                    FrameMarker::Tag(t) => tag = Some(*t),
+                    FrameMarker::Repeat(n) => repeat = Some(*n),
+                    FrameMarker::Note(n) => note = Some(*n),
+                    marker => {
+                        write_boundary(w, marker)?;
+                        module = None;
+                        file = None;
+                    }
+                }
+            }
+
+            if opts.group_by_file {
+                if file != Some(frame.file) {
+                    writeln!(w, "{}:", frame.file)?;
+                    file = Some(frame.file);
+                }
+                w.write_str("  ")?;
+            } else if opts.group_by_module && !frame.module().is_empty() {
+                if module != Some(frame.module()) {
+                    writeln!(w, "in {}:", frame.module())?;
+                    module = Some(frame.module());
+                }
+                w.write_str("  ")?;
+            } else {
+                module = None;
+            }
+
+            let column = if opts.normalize_columns {
+                0
+            } else {
+                frame.column
+            };
+            if opts.hyperlinks {
+                write!(w, "\x1b]8;;{}\x1b\\", file_url(frame))?;
+            }
+            if opts.group_by_file {
+                write_styled(w, opts, GRAY, format_args!("{}:{}", frame.line, column))?;
+            } else {
+                let location = format_args!("{}:{}:{}", frame.file, frame.line, column);
+                write_styled(w, opts, GRAY, location)?;
+            }
+            if opts.hyperlinks {
+                w.write_str("\x1b]8;;\x1b\\")?;
+            }
+            if let Some(function) = frame.function() {
+                write!(w, " ({})", function)?;
+            }
+            if let Some(tag) = tag {
+                write!(w, " #{}", tag)?;
+            }
+            if let Some(note) = note {
+                write!(w, " - {}", note)?;
+            }
+            if let Some(n) = repeat {
+                write!(w, " (x{})", n)?;
+            }
+            #[cfg(feature = "timestamps")]
+            if let Some(times) = times.filter(|_| idx > 0) {
+                write!(w, " (+{:?})", times[idx].duration_since(times[0]))?;
+            }
+            w.write_char('\n')?;
+        }
+        for (_, marker) in markers {
+            write_boundary(w, marker)?;
+        }
+        if self.truncated_frames() > 0 {
+            writeln!(w, "... {} frames truncated", self.truncated_frames())?;
+        }
+        Ok(())
+    }
+}
+
+impl EzError {
+    /// Dumps everything that is known about the error into a multi-section
+    /// text that can be attached to bug reports. Unlike [`EzError::render`],
+    /// this also includes the metadata of the error, e.g. its codes, and
+    /// the module and markers of every frame. Sections that are empty or
+    /// disabled by features are omitted.
+    pub fn debug_report(&self) -> String {
+        let mut s = String::with_capacity(1024);
+        s.push_str("== Error ==\n");
+        let _ = writeln!(s, "name: {}", self.ty().name());
+        let _ = writeln!(s, "message: {}", self.ty());
+        let _ = writeln!(s, "label: {}", self.metric_label());
+        let _ = writeln!(s, "code: {}", self.numeric_code());
+        let _ = writeln!(s, "http status: {}", self.http_status());
+        let _ = writeln!(s, "builtin: {}", self.is_builtin());
+        let _ = writeln!(s, "transient: {}", self.is_transient());
+        let _ = writeln!(s, "reported: {}", self.is_reported());
+        if let Some(dur) = self.retry_after_duration() {
+            let _ = writeln!(s, "retry after: {:?}", dur);
+        }
+        if let Some(url) = self.url() {
+            let _ = writeln!(s, "url: {}", url);
+        }
+        if let Some(input) = self.input() {
+            let _ = writeln!(s, "input: {}", input);
+        }
+
+        write_section(&mut s, "Context", self.contexts());
+        write_section(&mut s, "Causes", self.causes());
+        if let ErrorType::Multiple(errors) = self.ty() {
+            let parts: Vec<String> = errors
+                .iter()
+                .map(|e| format!("{}: {}", e.ty().name(), e.ty()))
+                .collect();
+            write_section(&mut s, "Parts", &parts);
+        }
+
+        #[cfg(not(feature = "no_stacktrace"))]
+        {
+            let mut markers = self.markers().iter().peekable();
+            s.push_str("\n== Stacktrace ==\n");
+            for (idx, frame) in self.frames().iter().enumerate() {
+                let _ = write!(s, "#{} {}:{}:{}", idx, frame.file, frame.line, frame.column);
+                if let Some(function) = frame.function() {
+                    let _ = write!(s, " ({})", function);
+                }
+                if !frame.module().is_empty() {
+                    let _ = write!(s, " in {}", frame.module());
+                }
+                while let Some((_, marker)) = markers.next_if(|(i, _)| *i == idx) {
+                    match marker {
+                        FrameMarker::Boundary(label) => {
+                            let _ = write!(s, " [boundary before: {}]", label);
+                        }
+                        FrameMarker::Tag(tag) => {
+                            let _ = write!(s, " [tag: {}]", tag);
+                        }
+                        FrameMarker::Repeat(n) => {
+                            let _ = write!(s, " [repeated: {}]", n);
+                        }
+                        FrameMarker::Note(note) => {
+                            let _ = write!(s, " [note: {}]", note);
+                        }
+                    }
+                }
+                s.push('\n');
+            }
+            for (_, marker) in markers {
+                if let FrameMarker::Boundary(label) = marker {
+                    let _ = writeln!(s, "[boundary: {}]", label);
+                }
+            }
+            if self.truncated_frames() > 0 {
+                let _ = writeln!(s, "... {} frames truncated", self.truncated_frames());
+            }
+        }
+
+        s
+    }
+}
+
+/// Writes a `debug_report` section with one line per entry, unless it is empty.
+fn write_section(s: &mut String, title: &str, lines: &[String]) {
+    if lines.is_empty() {
+        return;
+    }
+
+    let _ = writeln!(s, "\n== {} ==", title);
+    for line in lines {
+        s.push_str(line);
+        s.push('\n');
+    }
+}
+
+impl EzError {
+    /// Formats the error as a GitHub Actions `error` workflow command, which
+    /// shows up as an annotation in pull requests when printed during a CI
+    /// run. The location is taken from the origin frame (the first one) and
+    /// omitted if there is none.
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// const LOC: ConstLocation = ConstLocation::new("src/x.rs", 12, 5);
+    /// let mut err = EzError::message("first line\nsecond line");
+    /// err.add_frame(&LOC);
+    ///
+    /// assert_eq!(
+    ///     "::error file=src/x.rs,line=12,col=5::first line%0Asecond line",
+    ///     err.to_github_annotation()
+    /// );
+    /// ```
+    pub fn to_github_annotation(&self) -> String {
+        let mut s = String::from("::error");
+
+        #[cfg(not(feature = "no_stacktrace"))]
+        if let Some(frame) = self.frames().first() {
+            let _ = write!(
+                s,
+                " file={},line={},col={}",
+                escape_annotation_property(frame.file),
+                frame.line,
+                frame.column
+            );
+        }
+
+        s.push_str("::");
+        s.push_str(&escape_annotation_data(&self.ty().to_string()));
+        s
+    }
+}
+
+/// Escapes the message of a GitHub Actions workflow command.
+fn escape_annotation_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escapes a property value of a GitHub Actions workflow command.
+#[cfg(not(feature = "no_stacktrace"))]
+fn escape_annotation_property(value: &str) -> String {
+    escape_annotation_data(value)
+        .replace(':', "%3A")
+        .replace(',', "%2C")
+}
+
+/// Converts the error into the full text produced by [`EzError::render`],
+/// including the name, the message and the stacktrace. Use
+/// [`ErrorType::format`] on the type instead if only the message is needed.
+impl From<EzError> for String {
+    fn from(err: EzError) -> String {
+        err.render()
+    }
+}
+
+/// Returns the `file://` URL of the frame, pointing at its line.
+#[cfg(not(feature = "no_stacktrace"))]
+fn file_url(frame: &ConstLocation) -> String {
+    #[cfg(feature = "std")]
+    let path = {
+        let path = std::path::Path::new(frame.file);
+        let path = if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            std::env::current_dir().unwrap_or_default().join(path)
+        };
+        path.to_string_lossy().replace('\\', "/")
+    };
+    #[cfg(not(feature = "std"))]
+    let path = frame.file.replace('\\', "/");
+    let separator = if path.starts_with('/') { "" } else { "/" };
+    format!("file://{}{}#{}", separator, path, frame.line)
+}
+
+const BOLD_RED: &str = "\x1b[1;31m";
+#[cfg(not(feature = "no_stacktrace"))]
+const GRAY: &str = "\x1b[90m";
+const RESET: &str = "\x1b[0m";
+
+/// Writes `text` in the given ANSI style if [`HandleOptions::color`] is
+/// enabled, or as plain text otherwise.
+fn write_styled<W: Write + ?Sized>(
+    w: &mut W,
+    opts: &HandleOptions,
+    style: &str,
+    text: core::fmt::Arguments<'_>,
+) -> core::fmt::Result {
+    if opts.color {
+        write!(w, "{}{}{}", style, text, RESET)
+    } else {
+        w.write_fmt(text)
+    }
+}
+
+/// Writes a marker that is printed on its own line between frames.
+#[cfg(not(feature = "no_stacktrace"))]
+fn write_boundary<W: Write + ?Sized>(w: &mut W, marker: &FrameMarker) -> core::fmt::Result {
+    match marker {
+        FrameMarker::Boundary(label) => writeln!(w, "--- {} ---", label),
+        // Tags, repeat counts and notes are printed on the line of their frame.
+        FrameMarker::Tag(_) | FrameMarker::Repeat(_) | FrameMarker::Note(_) => Ok(()),
+    }
+}
+
+#[cfg(all(test, not(feature = "panic_on_error")))]
+mod tests {
+    use super::*;
+
+    const FIRST: ConstLocation = ConstLocation::new("src/first.rs", 10, 5);
+    const SECOND: ConstLocation = ConstLocation::new("src/second.rs", 20, 17);
+
+    fn two_frame_error() -> EzError {
+        let mut err = EzError::message("boom");
+        err.add_frame(&FIRST);
+        err.add_frame(&SECOND);
+        err
+    }
+
+    #[cfg(not(any(feature = "single_frame", feature = "timestamps")))]
+    #[test]
+    fn render() {
+        assert_eq!(
+            "Error Message: boom\n\nStacktrace:\nsrc/first.rs:10:5\nsrc/second.rs:20:17\n",
+            two_frame_error().render()
+        );
+    }
+
+    #[cfg(not(any(feature = "single_frame", feature = "timestamps")))]
+    #[test]
+    fn normalize_columns() {
+        let opts = HandleOptions {
+            normalize_columns: true,
+            ..HandleOptions::default()
+        };
+
+        assert_eq!(
+            "Error Message: boom\n\nStacktrace:\nsrc/first.rs:10:0\nsrc/second.rs:20:0\n",
+            two_frame_error().render_with(&opts)
+        );
+    }
+
+    #[cfg(not(any(feature = "single_frame", feature = "timestamps")))]
+    #[test]
+    fn render_context() {
+        let err = two_frame_error().context("loading config");
+
+        assert_eq!(
+            "Error Message: boom\n\nContext:\nloading config\n\nStacktrace:\nsrc/first.rs:10:5\nsrc/second.rs:20:17\n",
+            err.render()
+        );
+    }
+
+    #[cfg(not(any(feature = "single_frame", feature = "timestamps")))]
+    #[test]
+    fn render_boundary() {
+        let mut err = EzError::message("boom");
+        err.add_frame(&FIRST);
+        err.add_boundary("crossed into storage layer");
+        err.add_frame(&SECOND);
+
+        assert_eq!(
+            "Error Message: boom\n\nStacktrace:\nsrc/first.rs:10:5\n--- crossed into storage layer ---\nsrc/second.rs:20:17\n",
+            err.render()
+        );
+    }
+
+    #[test]
+    fn render_hyperlinks() {
+        const ABSOLUTE: ConstLocation = ConstLocation::new("/work/src/main.rs", 12, 5);
+        let mut err = EzError::message("boom");
+        err.add_frame(&ABSOLUTE);
+        let opts = HandleOptions {
+            hyperlinks: true,
+            ..HandleOptions::default()
+        };
+
+        assert_eq!(
+            "Error Message: boom\n\nStacktrace:\n\x1b]8;;file:///work/src/main.rs#12\x1b\\/work/src/main.rs:12:5\x1b]8;;\x1b\\\n",
+            err.render_with(&opts)
+        );
+    }
+
+    #[cfg(not(any(feature = "single_frame", feature = "timestamps")))]
+    #[test]
+    fn render_color() {
+        let opts = HandleOptions {
+            color: true,
+            ..HandleOptions::default()
+        };
+
+        assert_eq!(
+            "Error \x1b[1;31mMessage\x1b[0m: boom\n\nStacktrace:\n\x1b[90msrc/first.rs:10:5\x1b[0m\n\x1b[90msrc/second.rs:20:17\x1b[0m\n",
+            two_frame_error().render_with(&opts)
+        );
+    }
+
+    #[cfg(not(any(feature = "single_frame", feature = "timestamps")))]
+    #[test]
+    fn into_string() {
+        let rendered = two_frame_error().render();
+        let s: String = two_frame_error().into();
+
+        assert_eq!(rendered, s);
+        assert!(s.starts_with("Error Message: boom\n"));
+        assert!(s.contains("src/second.rs:20:17"));
+    }
+
+    #[cfg(not(any(feature = "single_frame", feature = "timestamps")))]
+    #[test]
+    fn render_causes() {
+        let err = two_frame_error()
+            .because("connection reset")
+            .because(core::fmt::Error);
+
+        assert_eq!(
+            "Error Message: boom\nCaused by: connection reset\nCaused by: an error occurred when formatting an argument\n\nStacktrace:\nsrc/first.rs:10:5\nsrc/second.rs:20:17\n",
+            err.render()
+        );
+    }
+
+    #[test]
+    fn github_annotation() {
+        let err = two_frame_error();
+        assert_eq!(
+            "::error file=src/first.rs,line=10,col=5::boom",
+            err.to_github_annotation()
+        );
+
+        let err = EzError::message("100% broken\r\nreally");
+        assert_eq!(
+            "::error::100%25 broken%0D%0Areally",
+            err.to_github_annotation()
+        );
+    }
+
+    #[cfg(not(any(feature = "single_frame", feature = "timestamps")))]
+    #[test]
+    fn render_url() {
+        let err = two_frame_error().with_url("https://example.com/runbook");
+
+        assert_eq!(
+            "Error Message: boom\nsee: https://example.com/runbook\n\nStacktrace:\nsrc/first.rs:10:5\nsrc/second.rs:20:17\n",
+            err.render()
+        );
+    }
+
+    #[cfg(not(any(feature = "single_frame", feature = "timestamps")))]
+    #[test]
+    fn render_tags() {
+        let mut err = EzError::message("boom");
+        err.add_tagged_frame(&FIRST, 7);
+        err.add_frame(&SECOND);
+        let err = Err::<(), _>(err).loc_tagged(&FIRST, 42).unwrap_err();
+
+        assert_eq!(
+            "Error Message: boom\n\nStacktrace:\nsrc/first.rs:10:5 #7\nsrc/second.rs:20:17\nsrc/first.rs:10:5 #42\n",
+            err.render()
+        );
+    }
+
+    #[cfg(not(any(feature = "single_frame", feature = "timestamps")))]
+    #[test]
+    fn render_grouped_by_module() {
+        const STORAGE_READ: ConstLocation =
+            ConstLocation::new("src/storage.rs", 10, 5).with_module("app::storage");
+        const STORAGE_OPEN: ConstLocation =
+            ConstLocation::new("src/storage.rs", 42, 9).with_module("app::storage");
+        const API: ConstLocation = ConstLocation::new("src/api.rs", 7, 13).with_module("app::api");
+
+        let mut err = EzError::message("boom");
+        err.add_frame(&STORAGE_READ);
+        err.add_frame(&STORAGE_OPEN);
+        err.add_frame(&API);
+        err.add_frame(&FIRST);
+
+        let opts = HandleOptions {
+            group_by_module: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            "Error Message: boom\n\nStacktrace:\nin app::storage:\n  src/storage.rs:10:5\n  src/storage.rs:42:9\nin app::api:\n  src/api.rs:7:13\nsrc/first.rs:10:5\n",
+            err.render_with(&opts)
+        );
+    }
+
+    #[cfg(not(any(feature = "single_frame", feature = "timestamps")))]
+    #[test]
+    fn trace_grouped_by_file() {
+        let mut err = two_frame_error();
+        err.add_frame(&SECOND);
+        err.add_boundary("worker");
+        err.add_frame(&SECOND);
+        err.add_frame(&FIRST);
+
+        assert_eq!(
+            "src/first.rs:\n  10:5\nsrc/second.rs:\n  20:17\n  20:17\n--- worker ---\nsrc/second.rs:\n  20:17\nsrc/first.rs:\n  10:5\n",
+            err.trace_string_grouped()
+        );
+    }
+
+    #[cfg(not(feature = "single_frame"))]
+    #[test]
+    fn debug_report() {
+        let mut err = two_frame_error()
+            .context("loading the config")
+            .because("permission denied");
+        err.add_tagged_frame(&FIRST, 3);
+
+        assert_eq!(
+            "== Error ==\nname: Message\nmessage: boom\nlabel: message\ncode: 9\nhttp status: 500\nbuiltin: false\ntransient: false\nreported: false\n\
+             \n== Context ==\nloading the config\n\
+             \n== Causes ==\npermission denied\n\
+             \n== Stacktrace ==\n#0 src/first.rs:10:5\n#1 src/second.rs:20:17\n#2 src/first.rs:10:5 [tag: 3]\n",
+            err.debug_report()
+        );
+    }
+
+    #[cfg(not(any(feature = "single_frame", feature = "timestamps")))]
+    #[test]
+    fn trace_string() {
+        let mut err = two_frame_error();
+        err.add_boundary("worker");
+
+        assert_eq!(
+            "src/first.rs:10:5\nsrc/second.rs:20:17\n--- worker ---\n",
+            err.trace_string()
+        );
+        assert_eq!("", EzError::message("no frames").trace_string());
+    }
+
+    #[cfg(not(any(feature = "single_frame", feature = "timestamps")))]
+    #[test]
+    fn write_trace() {
+        let mut err = two_frame_error();
+        err.add_boundary("worker");
+
+        let mut s = String::from("Trace:\n");
+        err.write_trace(&mut s).unwrap();
+        assert_eq!(
+            "Trace:\nsrc/first.rs:10:5\nsrc/second.rs:20:17\n--- worker ---\n",
+            s
+        );
+    }
+}
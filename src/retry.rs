@@ -0,0 +1,200 @@
+//! Helpers for retrying operations that can fail temporarily.
+
+use crate::alloc_prelude::*;
+use crate::core::*;
+use std::time::Duration;
+
+/// Calls `func` up to `attempts` times until it succeeds, without waiting
+/// between the attempts. Unlike [`retry_backoff`], every error is kept: if
+/// no attempt succeeds, an [`ErrorType::Multiple`] that contains the error
+/// of each attempt in order is returned, with a context message that
+/// contains the number of attempts. At least one attempt is always made.
+///
+/// ```
+/// # use ez_err::prelude::*;
+/// let mut calls = 0;
+/// let err = retry::<_, ()>(2, || {
+///     calls += 1;
+///     Err(EzError::message(&format!("attempt {} failed", calls))).loc(flc!())
+/// })
+/// .unwrap_err();
+///
+/// assert_eq!(2, err.parts_iter().count());
+/// assert_eq!(&["gave up after 2 attempts".to_owned()], err.contexts());
+/// ```
+pub fn retry<F, R>(attempts: usize, mut func: F) -> Result<R>
+where
+    F: FnMut() -> Result<R>,
+{
+    let attempts = attempts.max(1);
+    let mut errors = Vec::new();
+    while errors.len() < attempts {
+        match func() {
+            Ok(value) => return Ok(value),
+            Err(err) => errors.push(err),
+        }
+    }
+
+    Err(EzError::aggregate(errors).context(&format!(
+        "gave up after {} attempt{}",
+        attempts,
+        if attempts == 1 { "" } else { "s" }
+    )))
+}
+
+/// Calls `func` up to `attempts` times until it succeeds. After a failed
+/// attempt the thread sleeps with exponential backoff (`base`, `2 * base`,
+/// `4 * base`, ...) before trying again. If the error suggests a wait with
+/// [`EzError::retry_after`], that wait is used for this attempt instead.
+/// Errors that are not marked with [`EzError::transient`] are returned
+/// immediately, since trying again will not help.
+///
+/// If no attempt succeeds, the last error is returned with a context message
+/// that contains the number of attempts and the total time spent waiting.
+/// At least one attempt is always made.
+///
+/// ```no_run
+/// # use ez_err::prelude::*;
+/// # use std::time::Duration;
+/// # fn connect() -> Result<()> { Ok(()) }
+/// let conn = retry_backoff(5, Duration::from_millis(100), || {
+///     connect().map_err(EzError::transient)
+/// });
+/// ```
+pub fn retry_backoff<F, R>(attempts: usize, base: Duration, mut func: F) -> Result<R>
+where
+    F: FnMut() -> Result<R>,
+{
+    let attempts = attempts.max(1);
+    let mut delay = base;
+    let mut waited = Duration::ZERO;
+    let mut attempt = 1;
+    loop {
+        match func() {
+            Ok(value) => return Ok(value),
+            Err(err) if attempt == attempts || !err.is_transient() => {
+                return Err(err.context(&format!(
+                    "gave up after {} attempt{} and {:?} of waiting",
+                    attempt,
+                    if attempt == 1 { "" } else { "s" },
+                    waited
+                )));
+            }
+            Err(err) => {
+                let wait = err.retry_after_duration().unwrap_or(delay);
+                std::thread::sleep(wait);
+                waited += wait;
+                delay = delay.checked_mul(2).unwrap_or(delay);
+                attempt += 1;
+            }
+        }
+    }
+}
+
+#[cfg(all(test, not(feature = "panic_on_error")))]
+mod tests {
+    use super::*;
+    use crate::flc;
+
+    #[test]
+    fn retry_succeeds_on_third_attempt() {
+        let mut calls = 0;
+        let res = retry(5, || {
+            calls += 1;
+            if calls < 3 {
+                Err(EzError::message("busy"))
+            } else {
+                Ok(calls)
+            }
+        });
+
+        assert_eq!(Ok(3), res);
+        assert_eq!(3, calls);
+    }
+
+    #[test]
+    fn retry_keeps_every_error() {
+        let mut calls = 0;
+        let err = retry::<_, ()>(3, || {
+            calls += 1;
+            Err(EzError::message(&format!("attempt {}", calls))).loc(flc!())
+        })
+        .unwrap_err();
+
+        assert_eq!(3, calls);
+        assert_eq!(&["gave up after 3 attempts".to_owned()], err.contexts());
+        let parts: Vec<&EzError> = err.parts_iter().collect();
+        assert_eq!(3, parts.len());
+        for (idx, part) in parts.iter().enumerate() {
+            let msg = format!("attempt {}", idx + 1);
+            assert_eq!(&ErrorType::Message(msg), part.ty());
+            assert_eq!(1, part.frames().len());
+        }
+    }
+
+    #[test]
+    fn succeeds_on_last_attempt() {
+        let mut calls = 0;
+        let res = retry_backoff(3, Duration::from_millis(1), || {
+            calls += 1;
+            if calls < 3 {
+                Err(EzError::message("busy").transient())
+            } else {
+                Ok(calls)
+            }
+        });
+
+        assert_eq!(Ok(3), res);
+        assert_eq!(3, calls);
+    }
+
+    #[test]
+    fn gives_up() {
+        let mut calls = 0;
+        let err = retry_backoff::<_, ()>(3, Duration::from_millis(1), || {
+            calls += 1;
+            Err(EzError::message("busy").transient())
+        })
+        .unwrap_err();
+
+        assert_eq!(3, calls);
+        assert_eq!(
+            "gave up after 3 attempts and 3ms of waiting",
+            err.contexts()[0]
+        );
+    }
+
+    #[test]
+    fn stops_on_permanent_error() {
+        let mut calls = 0;
+        let err = retry_backoff::<_, ()>(3, Duration::from_millis(1), || {
+            calls += 1;
+            Err(EzError::message("invalid"))
+        })
+        .unwrap_err();
+
+        assert_eq!(1, calls);
+        assert_eq!(
+            "gave up after 1 attempt and 0ns of waiting",
+            err.contexts()[0]
+        );
+    }
+
+    #[test]
+    fn honors_retry_after() {
+        let mut calls = 0;
+        let err = retry_backoff::<_, ()>(2, Duration::from_secs(60), || {
+            calls += 1;
+            Err(EzError::message("rate limited").retry_after(Duration::from_millis(2)))
+        })
+        .unwrap_err();
+
+        assert_eq!(2, calls);
+        assert_eq!(Some(Duration::from_millis(2)), err.retry_after_duration());
+        assert!(err.is_transient());
+        assert_eq!(
+            "gave up after 2 attempts and 2ms of waiting",
+            err.contexts()[0]
+        );
+    }
+}
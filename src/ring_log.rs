@@ -0,0 +1,87 @@
+//! A fixed-size in-memory log of recent errors.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// Keeps the output of the most recent errors in memory, e.g. to attach
+/// them to a crash report. Once the capacity is reached, the oldest entry
+/// is evicted. The log can be shared between threads.
+///
+/// Errors are pushed into the log when they are handled or reported with
+/// [`HandleOptions::ring_log`] set.
+///
+/// ```
+/// # use ez_err::prelude::*;
+/// use std::sync::Arc;
+///
+/// let ring_log = Arc::new(RingLog::new(16));
+/// let mut opts = HandleOptions::default();
+/// opts.ring_log = Some(ring_log.clone());
+///
+/// Err::<(), _>(EzError::message("oops")).handle_with_options(&opts);
+/// assert_eq!(1, ring_log.dump().len());
+/// ```
+///
+/// [`HandleOptions::ring_log`]: crate::render::HandleOptions::ring_log
+#[derive(Debug)]
+pub struct RingLog {
+    capacity: usize,
+    entries: Mutex<VecDeque<String>>,
+}
+
+impl RingLog {
+    /// Creates a new [`RingLog`] that keeps at most `capacity` entries.
+    pub fn new(capacity: usize) -> RingLog {
+        RingLog {
+            capacity,
+            entries: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// Returns the maximum number of entries kept by the log.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Adds an entry to the log, evicting the oldest one if the log is full.
+    pub fn push(&self, entry: String) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if entries.len() == self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+
+    /// Returns a copy of all entries, starting with the oldest one.
+    pub fn dump(&self) -> Vec<String> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.iter().cloned().collect()
+    }
+}
+
+#[cfg(all(test, not(feature = "panic_on_error")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_oldest() {
+        let ring_log = RingLog::new(3);
+        for i in 0..5 {
+            ring_log.push(format!("error {}", i));
+        }
+
+        assert_eq!(vec!["error 2", "error 3", "error 4"], ring_log.dump());
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let ring_log = RingLog::new(0);
+        ring_log.push("error".into());
+
+        assert!(ring_log.dump().is_empty());
+    }
+}
@@ -0,0 +1,104 @@
+//! Scopes that attach context to all errors created inside of them.
+
+use std::cell::RefCell;
+use std::marker::PhantomData;
+
+thread_local! {
+    static SCOPES: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Enters a new [`ErrorScope`] with a formatted message. While the returned
+/// guard is alive, every [`EzError`] created on the current thread gets the
+/// message as context.
+///
+/// ```
+/// # use ez_err::prelude::*;
+/// let _scope = error_scope!("processing batch {}", 7);
+/// let err = EzError::message("invalid row");
+/// assert_eq!(&["processing batch 7".to_owned()][..], err.contexts());
+/// ```
+///
+/// [`EzError`]: crate::core::EzError
+#[macro_export]
+macro_rules! error_scope {
+    ($($args:tt)*) => {
+        $crate::scope::ErrorScope::enter(::std::format!($($args)*))
+    };
+}
+
+/// A guard that attaches a context message to every [`EzError`] created on
+/// the current thread while it is alive. Nested scopes accumulate, starting
+/// with the outermost one. The scope is left when the guard is dropped.
+///
+/// [`EzError`]: crate::core::EzError
+#[must_use = "the scope is left as soon as the guard is dropped"]
+#[derive(Debug)]
+pub struct ErrorScope {
+    // The scope stack is thread-local, so the guard must not leave its thread.
+    _not_send: PhantomData<*const ()>,
+}
+
+impl ErrorScope {
+    /// Enters a new scope with the given message. Usually called through
+    /// [`error_scope!`].
+    pub fn enter(msg: String) -> ErrorScope {
+        SCOPES.with(|scopes| scopes.borrow_mut().push(msg));
+        ErrorScope {
+            _not_send: PhantomData,
+        }
+    }
+}
+
+impl Drop for ErrorScope {
+    fn drop(&mut self) {
+        SCOPES.with(|scopes| scopes.borrow_mut().pop());
+    }
+}
+
+/// Returns the messages of all scopes of the current thread, starting
+/// with the outermost one.
+pub(crate) fn current_scopes() -> Vec<String> {
+    SCOPES.with(|scopes| scopes.borrow().clone())
+}
+
+#[cfg(all(test, not(feature = "panic_on_error")))]
+mod tests {
+    use crate::core::*;
+
+    #[test]
+    fn nested_scopes() {
+        let _outer = error_scope!("processing batch {}", 7);
+        {
+            let _inner = error_scope!("reading row {}", 3);
+            let err = EzError::message("invalid row");
+            assert_eq!(
+                &["processing batch 7".to_owned(), "reading row 3".to_owned()][..],
+                err.contexts()
+            );
+        }
+
+        let err = EzError::message("invalid batch").context("after the scope");
+        assert_eq!(
+            &[
+                "processing batch 7".to_owned(),
+                "after the scope".to_owned()
+            ][..],
+            err.contexts()
+        );
+    }
+
+    #[test]
+    fn merge_in_scope() {
+        let first = EzError::message("invalid row").context("reading row 3");
+        let second = EzError::new(ErrorType::EmptySlice);
+
+        let _scope = error_scope!("processing batch {}", 7);
+        let err = first.with(second);
+        assert_eq!(&["reading row 3".to_owned()][..], err.contexts());
+    }
+
+    #[test]
+    fn no_scope() {
+        assert!(EzError::message("unscoped").contexts().is_empty());
+    }
+}
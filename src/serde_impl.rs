@@ -0,0 +1,243 @@
+//! Serialization support for the `serde` feature.
+//!
+//! Frames are `&'static` references, so deserialized locations are interned
+//! and kept for the rest of the program, just like the locations added with
+//! [`EzError::add_std_location`]. The function name of a location is
+//! serialized, but it can not be restored.
+//!
+//! The interned memory is never freed, so it is limited to protect against
+//! untrusted input with many distinct locations or long strings. Once a limit
+//! is reached, new strings are replaced by [`UNKNOWN`] and new locations by a
+//! location in the file [`UNKNOWN`] at line and column `0`.
+
+#[cfg(not(feature = "no_stacktrace"))]
+use crate::core::FrameMarker;
+use crate::core::{try_intern_location, ConstLocation};
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use std::collections::BTreeSet;
+use std::io::ErrorKind;
+use std::sync::Mutex;
+
+/// Replaces strings that are deserialized after the interner is full.
+const UNKNOWN: &str = "<unknown>";
+/// The maximum number of bytes of all interned strings.
+const MAX_INTERNED_BYTES: usize = 1 << 20;
+/// The maximum number of interned locations, including the ones interned by
+/// [`EzError::add_std_location`].
+const MAX_INTERNED_LOCATIONS: usize = 1 << 14;
+
+/// The interned strings and the number of bytes they use.
+static INTERNED_STRS: Mutex<(BTreeSet<&'static str>, usize)> = Mutex::new((BTreeSet::new(), 0));
+
+/// Returns a `'static` copy of the string. Each distinct string is leaked
+/// once and reused afterwards.
+fn intern_str(value: String) -> &'static str {
+    let mut guard = INTERNED_STRS.lock().unwrap_or_else(|e| e.into_inner());
+    let (interned, bytes) = &mut *guard;
+    match interned.get(value.as_str()) {
+        Some(s) => s,
+        None if *bytes + value.len() > MAX_INTERNED_BYTES => UNKNOWN,
+        None => {
+            *bytes += value.len();
+            let s = Box::leak(value.into_boxed_str());
+            interned.insert(s);
+            s
+        }
+    }
+}
+
+/// The owned version of [`FrameMarker`].
+#[cfg(not(feature = "no_stacktrace"))]
+#[derive(serde::Deserialize)]
+enum MarkerRepr {
+    Boundary(String),
+    Tag(u64),
+    Repeat(usize),
+    Note(String),
+}
+
+/// Deserializes the markers of an error, interning their labels.
+#[cfg(not(feature = "no_stacktrace"))]
+pub(crate) fn deserialize_markers<'de, D>(
+    deserializer: D,
+) -> Result<Vec<(usize, FrameMarker)>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let markers = Vec::<(usize, MarkerRepr)>::deserialize(deserializer)?;
+    Ok(markers
+        .into_iter()
+        .map(|(idx, marker)| {
+            let marker = match marker {
+                MarkerRepr::Boundary(label) => FrameMarker::Boundary(intern_str(label)),
+                MarkerRepr::Tag(tag) => FrameMarker::Tag(tag),
+                MarkerRepr::Repeat(n) => FrameMarker::Repeat(n),
+                MarkerRepr::Note(note) => FrameMarker::Note(intern_str(note)),
+            };
+            (idx, marker)
+        })
+        .collect())
+}
+
+#[derive(serde::Deserialize)]
+struct LocationRepr {
+    file: String,
+    line: u32,
+    column: u32,
+    #[serde(default)]
+    module: String,
+}
+
+impl LocationRepr {
+    fn intern(self) -> &'static ConstLocation {
+        const UNKNOWN_LOCATION: ConstLocation = ConstLocation::new(UNKNOWN, 0, 0);
+
+        try_intern_location(
+            intern_str(self.file),
+            self.line,
+            self.column,
+            intern_str(self.module),
+            MAX_INTERNED_LOCATIONS,
+        )
+        .unwrap_or(&UNKNOWN_LOCATION)
+    }
+}
+
+/// Deserializes the `'static` target of an [`ErrorType::Parse`] by interning it.
+///
+/// [`ErrorType::Parse`]: crate::core::ErrorType::Parse
+pub(crate) fn deserialize_static_str<'de, D>(deserializer: D) -> Result<&'static str, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    String::deserialize(deserializer).map(intern_str)
+}
+
+/// Deserializes the frames of an error into interned locations.
+#[cfg(not(feature = "no_stacktrace"))]
+pub(crate) fn deserialize_frames<'de, D>(
+    deserializer: D,
+) -> Result<Vec<&'static ConstLocation>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let frames = Vec::<LocationRepr>::deserialize(deserializer)?;
+    Ok(frames.into_iter().map(LocationRepr::intern).collect())
+}
+
+impl Serialize for ConstLocation {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("ConstLocation", 5)?;
+        s.serialize_field("file", self.file)?;
+        s.serialize_field("line", &self.line)?;
+        s.serialize_field("column", &self.column)?;
+        s.serialize_field("module", self.module())?;
+        s.serialize_field("function", &self.function())?;
+        s.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for &'static ConstLocation {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        LocationRepr::deserialize(deserializer).map(LocationRepr::intern)
+    }
+}
+
+/// Serializes an [`ErrorKind`] by its name.
+pub(crate) mod io_kind {
+    use super::*;
+
+    const KINDS: &[(ErrorKind, &str)] = &[
+        (ErrorKind::NotFound, "NotFound"),
+        (ErrorKind::PermissionDenied, "PermissionDenied"),
+        (ErrorKind::ConnectionRefused, "ConnectionRefused"),
+        (ErrorKind::ConnectionReset, "ConnectionReset"),
+        (ErrorKind::ConnectionAborted, "ConnectionAborted"),
+        (ErrorKind::NotConnected, "NotConnected"),
+        (ErrorKind::AddrInUse, "AddrInUse"),
+        (ErrorKind::AddrNotAvailable, "AddrNotAvailable"),
+        (ErrorKind::BrokenPipe, "BrokenPipe"),
+        (ErrorKind::AlreadyExists, "AlreadyExists"),
+        (ErrorKind::WouldBlock, "WouldBlock"),
+        (ErrorKind::InvalidInput, "InvalidInput"),
+        (ErrorKind::InvalidData, "InvalidData"),
+        (ErrorKind::TimedOut, "TimedOut"),
+        (ErrorKind::WriteZero, "WriteZero"),
+        (ErrorKind::Interrupted, "Interrupted"),
+        (ErrorKind::Unsupported, "Unsupported"),
+        (ErrorKind::UnexpectedEof, "UnexpectedEof"),
+        (ErrorKind::OutOfMemory, "OutOfMemory"),
+    ];
+
+    pub(crate) fn serialize<S: Serializer>(
+        kind: &ErrorKind,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let name = KINDS
+            .iter()
+            .find(|(k, _)| k == kind)
+            .map_or("Other", |(_, name)| name);
+        serializer.serialize_str(name)
+    }
+
+    /// Unknown kinds are deserialized as [`ErrorKind::Other`].
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<ErrorKind, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Ok(KINDS
+            .iter()
+            .find(|(_, n)| *n == name)
+            .map_or(ErrorKind::Other, |(kind, _)| *kind))
+    }
+}
+
+#[cfg(all(test, not(feature = "panic_on_error")))]
+mod tests {
+    use super::*;
+    use crate::core::{ErrorType, EzError};
+
+    #[test]
+    fn round_trip() {
+        const FIRST: ConstLocation =
+            ConstLocation::new("src/worker.rs", 12, 5).with_module("app::worker");
+        const SECOND: ConstLocation = ConstLocation::new("src/main.rs", 40, 9);
+
+        let mut err =
+            EzError::custom(7, "JobFailed".into(), "job 3 failed".into()).context("running jobs");
+        err.add_frame(&FIRST);
+        err.add_boundary("coordinator");
+        err.add_tagged_frame(&SECOND, 3);
+
+        let json = serde_json::to_string(&err).unwrap();
+        let back: EzError = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(err, back);
+        assert_eq!(err.render(), back.render());
+        assert_eq!("app::worker", back.frames()[0].module());
+    }
+
+    #[test]
+    fn io_kind() {
+        let err = EzError::new(ErrorType::Io(ErrorKind::NotFound, "no file".into()));
+
+        let json = serde_json::to_string(&err).unwrap();
+        assert!(json.contains(r#"{"Io":["NotFound","no file"]}"#));
+        let back: EzError = serde_json::from_str(&json).unwrap();
+        assert_eq!(err, back);
+    }
+
+    #[test]
+    fn parse_target() {
+        let err = EzError::new(ErrorType::Parse {
+            input: "x1".into(),
+            target: "u64",
+        });
+
+        let json = serde_json::to_string(&err).unwrap();
+        assert!(json.contains(r#"{"Parse":{"input":"x1","target":"u64"}}"#));
+        let back: EzError = serde_json::from_str(&json).unwrap();
+        assert_eq!(err, back);
+    }
+}
@@ -1,10 +1,14 @@
 //! Slice extensions that integrate well with the error handling system.
 
+use crate::alloc_prelude::*;
 use crate::core::*;
 use crate::flc;
-use std::ops;
+use alloc::collections::VecDeque;
+use core::ops;
+use core::slice;
 
-/// Extension trait for slices.
+/// Extension trait for slices. Element access by position is also
+/// implemented for [`VecDeque`].
 pub trait SliceExt<I, O>
 where
     O: ?Sized,
@@ -28,6 +32,64 @@ where
     /// assert_eq!(&ErrorType::RangeOutOfBounds(0, 4, 3), v.eget(0..4).err().unwrap().ty());
     /// ```
     fn eget(&self, index: I) -> Result<&O>;
+
+    /// Returns a reference to an element or subslice like [`eget`], or
+    /// [`None`] if the index is out of bounds. No [`EzError`] is created on
+    /// the failure path, which makes this cheaper than `eget(...).ok()` in
+    /// hot loops that skip missing indices. The default implementation is
+    /// `eget(...).ok()`, which implementations should override.
+    ///
+    /// [`eget`]: SliceExt::eget
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// let v = [10, 40, 30];
+    /// assert_eq!(Some(&40), v.eget_opt(1));
+    /// assert_eq!(None, v.eget_opt(3));
+    /// assert_eq!(Some(&[40, 30][..]), v.eget_opt(1..));
+    /// assert_eq!(None, v.eget_opt(2..1));
+    /// ```
+    #[inline]
+    fn eget_opt(&self, index: I) -> Option<&O> {
+        self.eget(index).ok()
+    }
+
+    /// Returns a copy of the element like [`eget`], which avoids keeping a
+    /// reference around for `Copy` types such as integers. The error is the
+    /// same as the one of [`eget`].
+    ///
+    /// [`eget`]: SliceExt::eget
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// let v = [10, 40, 30];
+    /// assert_eq!(Ok(50), v.eget_copied(0).map(|x| x + 40));
+    /// assert_eq!(&ErrorType::IndexOutOfBounds(3, 3), v.eget_copied(3).unwrap_err().ty());
+    /// ```
+    #[inline]
+    fn eget_copied(&self, index: I) -> Result<O>
+    where
+        O: Copy,
+    {
+        self.eget(index).copied()
+    }
+
+    /// Returns a clone of the element like [`eget`]. See [`eget_copied`] for
+    /// `Copy` types.
+    ///
+    /// [`eget`]: SliceExt::eget
+    /// [`eget_copied`]: SliceExt::eget_copied
+    #[inline]
+    fn eget_cloned(&self, index: I) -> Result<O>
+    where
+        O: Clone,
+    {
+        self.eget(index).cloned()
+    }
 }
 
 /// Mutable version of [`SliceExt`].
@@ -54,6 +116,461 @@ where
     fn eget_mut(&mut self, index: I) -> Result<&mut O>;
 }
 
+/// Fetches the element at the same index from several slices at once and
+/// returns them as a tuple. If the index is out of bounds for any of the
+/// slices, the error of the first such slice is returned together with a
+/// context message naming its position (starting at 0).
+///
+/// # Examples
+///
+/// ```
+/// # use ez_err::prelude::*;
+/// let ids = [1, 2, 3];
+/// let names = ["a", "b", "c"];
+/// let scores = vec![0.5, 0.7];
+///
+/// assert_eq!(Ok((&2, &"b", &0.7)), zip_eget!(1, ids, names, scores));
+/// assert_eq!(
+///     &["in slice 2 of zip_eget!".to_owned()][..],
+///     zip_eget!(2, ids, names, scores).err().unwrap().contexts()
+/// );
+/// ```
+#[macro_export]
+macro_rules! zip_eget {
+    ($index:expr, $($slice:expr),+ $(,)?) => {{
+        let index: usize = $index;
+        let mut position = 0usize;
+        let mut next_position = || {
+            position += 1;
+            position - 1
+        };
+        'zip: {
+            Ok(($(
+                match SliceExt::eget(&$slice[..], index) {
+                    Ok(value) => {
+                        next_position();
+                        value
+                    }
+                    Err(e) => {
+                        let msg = $crate::__private::format!("in slice {} of zip_eget!", next_position());
+                        break 'zip Err(e.context(&msg)).loc(flc!());
+                    }
+                },
+            )+))
+        }
+    }};
+}
+
+/// Extension trait for [`Result`]s that contain a slice, allowing `eget` to be
+/// chained directly onto a fallible producer.
+pub trait ResultSliceExt<T> {
+    /// The type of the element returned on success.
+    type Output;
+
+    /// Indexes into the contained slice if the [`Result`] is [`Ok`], otherwise
+    /// propagates the existing error. A frame is recorded in both cases.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// fn producer() -> Result<&'static [i32]> {
+    ///     Ok(&[10, 40, 30])
+    /// }
+    ///
+    /// assert_eq!(Ok(&40), producer().eget(1));
+    /// assert!(producer().eget(3).is_err());
+    /// ```
+    fn eget(self, index: usize) -> Result<Self::Output>;
+}
+
+impl<'a, T> ResultSliceExt<T> for Result<&'a [T]> {
+    type Output = &'a T;
+
+    #[inline]
+    fn eget(self, index: usize) -> Result<&'a T> {
+        let slice = self.loc(flc!())?;
+        slice.eget(index).loc(flc!())
+    }
+}
+
+impl<T> ResultSliceExt<T> for Result<Vec<T>> {
+    type Output = T;
+
+    #[inline]
+    fn eget(self, index: usize) -> Result<T> {
+        let vec = self.loc(flc!())?;
+        let len = vec.len();
+        match vec.into_iter().nth(index) {
+            Some(v) => Ok(v),
+            None => Err(EzError::new(ErrorType::IndexOutOfBounds(index, len))).loc(flc!()),
+        }
+    }
+}
+
+/// Extension trait for indexing slices and arrays with a constant index.
+///
+/// This is separate from [`SliceExt`] because a method without an index
+/// argument could not tell which of its index types is meant.
+pub trait ConstIndexExt<T> {
+    /// Returns a reference to the element at the constant index `N` like
+    /// [`eget`], or [`Err(_)`] with [`ErrorType::IndexOutOfBounds`] if it is
+    /// out of bounds. For arrays the length is known as well, so the bounds
+    /// check is resolved at compile time.
+    ///
+    /// [`eget`]: SliceExt::eget
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// let v = [10, 40, 30];
+    /// assert_eq!(Ok(&30), v.eget_const::<2>());
+    /// assert_eq!(&ErrorType::IndexOutOfBounds(3, 3), v.eget_const::<3>().unwrap_err().ty());
+    /// ```
+    fn eget_const<const N: usize>(&self) -> Result<&T>;
+}
+
+impl<T> ConstIndexExt<T> for [T] {
+    #[inline]
+    fn eget_const<const N: usize>(&self) -> Result<&T> {
+        if N < self.len() {
+            Ok(unsafe { self.get_unchecked(N) })
+        } else {
+            Err(EzError::new(ErrorType::IndexOutOfBounds(N, self.len()))).loc(flc!())
+        }
+    }
+}
+
+impl<T, const M: usize> ConstIndexExt<T> for [T; M] {
+    #[inline]
+    fn eget_const<const N: usize>(&self) -> Result<&T> {
+        if N < M {
+            Ok(unsafe { self.get_unchecked(N) })
+        } else {
+            Err(EzError::new(ErrorType::IndexOutOfBounds(N, M))).loc(flc!())
+        }
+    }
+}
+
+/// Extension trait for slices that need at least one element.
+pub trait NonEmptyExt<T> {
+    /// Returns the first element and the rest of the slice, or
+    /// [`Err(_)`] with [`ErrorType::EmptySlice`] if the slice is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// let v = [10, 40, 30];
+    /// assert_eq!(Ok((&10, &[40, 30][..])), v.esplit_first());
+    ///
+    /// let empty: &[i32] = &[];
+    /// assert_eq!(&ErrorType::EmptySlice, empty.esplit_first().err().unwrap().ty());
+    /// ```
+    fn esplit_first(&self) -> Result<(&T, &[T])>;
+
+    /// Returns the last element and the rest of the slice, or
+    /// [`Err(_)`] with [`ErrorType::EmptySlice`] if the slice is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// let v = [10, 40, 30];
+    /// assert_eq!(Ok((&30, &[10, 40][..])), v.esplit_last());
+    /// ```
+    fn esplit_last(&self) -> Result<(&T, &[T])>;
+
+    /// Returns the element at the index like [`SliceExt::eget`], but tells an
+    /// empty slice apart from an index that is too large: it fails with
+    /// [`ErrorType::EmptySlice`] if the slice is empty and with
+    /// [`ErrorType::IndexOutOfBounds`] otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// let v = [10, 40, 30];
+    /// assert_eq!(Ok(&40), v.eget_status(1));
+    /// assert_eq!(&ErrorType::IndexOutOfBounds(3, 3), v.eget_status(3).err().unwrap().ty());
+    ///
+    /// let empty: &[i32] = &[];
+    /// assert_eq!(&ErrorType::EmptySlice, empty.eget_status(0).err().unwrap().ty());
+    /// ```
+    fn eget_status(&self, index: usize) -> Result<&T>;
+
+    /// Returns the first element, or [`Err(_)`] with
+    /// [`ErrorType::EmptySlice`] if the slice is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// let v = [10, 40, 30];
+    /// assert_eq!(Ok(&10), v.efirst());
+    ///
+    /// let empty: &[i32] = &[];
+    /// assert_eq!(&ErrorType::EmptySlice, empty.efirst().unwrap_err().ty());
+    /// ```
+    fn efirst(&self) -> Result<&T>;
+
+    /// Returns the last element, or [`Err(_)`] with
+    /// [`ErrorType::EmptySlice`] if the slice is empty.
+    fn elast(&self) -> Result<&T>;
+
+    /// Mutable version of [`NonEmptyExt::efirst`].
+    fn efirst_mut(&mut self) -> Result<&mut T>;
+
+    /// Mutable version of [`NonEmptyExt::elast`].
+    fn elast_mut(&mut self) -> Result<&mut T>;
+}
+
+impl<T> NonEmptyExt<T> for [T] {
+    #[inline]
+    fn esplit_first(&self) -> Result<(&T, &[T])> {
+        match self.split_first() {
+            Some(split) => Ok(split),
+            None => Err(EzError::new(ErrorType::EmptySlice)).loc(flc!()),
+        }
+    }
+
+    #[inline]
+    fn esplit_last(&self) -> Result<(&T, &[T])> {
+        match self.split_last() {
+            Some(split) => Ok(split),
+            None => Err(EzError::new(ErrorType::EmptySlice)).loc(flc!()),
+        }
+    }
+
+    #[inline]
+    fn eget_status(&self, index: usize) -> Result<&T> {
+        if self.is_empty() {
+            Err(EzError::new(ErrorType::EmptySlice)).loc(flc!())
+        } else {
+            self.eget(index).loc(flc!())
+        }
+    }
+
+    #[inline]
+    fn efirst(&self) -> Result<&T> {
+        match self.first() {
+            Some(v) => Ok(v),
+            None => Err(EzError::new(ErrorType::EmptySlice)).loc(flc!()),
+        }
+    }
+
+    #[inline]
+    fn elast(&self) -> Result<&T> {
+        match self.last() {
+            Some(v) => Ok(v),
+            None => Err(EzError::new(ErrorType::EmptySlice)).loc(flc!()),
+        }
+    }
+
+    #[inline]
+    fn efirst_mut(&mut self) -> Result<&mut T> {
+        match self.first_mut() {
+            Some(v) => Ok(v),
+            None => Err(EzError::new(ErrorType::EmptySlice)).loc(flc!()),
+        }
+    }
+
+    #[inline]
+    fn elast_mut(&mut self) -> Result<&mut T> {
+        match self.last_mut() {
+            Some(v) => Ok(v),
+            None => Err(EzError::new(ErrorType::EmptySlice)).loc(flc!()),
+        }
+    }
+}
+
+/// Extension trait for iterating over slices.
+pub trait SliceIterExt<T> {
+    /// Applies a fallible function to every element and collects the
+    /// results. Stops at the first error, which gets a frame and a context
+    /// message with the index of the failing element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// # #[cfg(feature = "std")] {
+    /// let v = ["1", "2", "x"];
+    /// let parse = |s: &&str| s.parse::<i32>().loc(flc!());
+    ///
+    /// assert_eq!(Ok(vec![1, 2]), v[..2].etry_map(parse));
+    /// assert_eq!(
+    ///     &["while mapping the element at index 2".to_owned()][..],
+    ///     v.etry_map(parse).err().unwrap().contexts()
+    /// );
+    /// # }
+    /// ```
+    fn etry_map<U, F>(&self, f: F) -> Result<Vec<U>>
+    where
+        F: FnMut(&T) -> Result<U>;
+
+    /// Returns an iterator over chunks of `size` elements like
+    /// [`slice::chunks`], or [`Err(_)`] with [`ErrorType::InvalidRange`] if
+    /// `size` is zero instead of panicking.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// let v = [1, 2, 3, 4, 5];
+    /// let sums: Vec<i32> = v.echunks(2).unwrap().map(|c| c.iter().sum()).collect();
+    /// assert_eq!(vec![3, 7, 5], sums);
+    /// assert_eq!(&ErrorType::InvalidRange, v.echunks(0).unwrap_err().ty());
+    /// ```
+    fn echunks(&self, size: usize) -> Result<slice::Chunks<'_, T>>;
+
+    /// Returns an iterator over all windows of `size` elements like
+    /// [`slice::windows`], or [`Err(_)`] with [`ErrorType::InvalidRange`] if
+    /// `size` is zero instead of panicking.
+    fn ewindows(&self, size: usize) -> Result<slice::Windows<'_, T>>;
+}
+
+impl<T> SliceIterExt<T> for [T] {
+    #[inline]
+    fn etry_map<U, F>(&self, mut f: F) -> Result<Vec<U>>
+    where
+        F: FnMut(&T) -> Result<U>,
+    {
+        let mut mapped = Vec::with_capacity(self.len());
+        for (idx, v) in self.iter().enumerate() {
+            match f(v) {
+                Ok(v) => mapped.push(v),
+                Err(e) => {
+                    let msg = format!("while mapping the element at index {}", idx);
+                    return Err(e.context(&msg)).loc(flc!());
+                }
+            }
+        }
+        Ok(mapped)
+    }
+
+    #[inline]
+    fn echunks(&self, size: usize) -> Result<slice::Chunks<'_, T>> {
+        if size == 0 {
+            return Err(zero_size_error("chunk")).loc(flc!());
+        }
+        Ok(self.chunks(size))
+    }
+
+    #[inline]
+    fn ewindows(&self, size: usize) -> Result<slice::Windows<'_, T>> {
+        if size == 0 {
+            return Err(zero_size_error("window")).loc(flc!());
+        }
+        Ok(self.windows(size))
+    }
+}
+
+/// Creates the error for a chunk or window size of zero.
+fn zero_size_error(kind: &str) -> EzError {
+    EzError::new(ErrorType::InvalidRange).context(&format!("{} size must not be zero", kind))
+}
+
+/// Extension trait for slices that store multi-dimensional data in
+/// row-major order.
+pub trait GridExt<T> {
+    /// Returns a reference to the element at the given multi-dimensional
+    /// position. `shape` holds the length of every dimension and `indices`
+    /// the position within each of them.
+    ///
+    /// Returns [`ErrorType::ShapeMismatch`] if the number of indices does not
+    /// match the number of dimensions, [`ErrorType::DimensionOutOfBounds`] if
+    /// an index is outside of its dimension, and [`ErrorType::IndexOutOfBounds`]
+    /// if the shape does not fit into the slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// // A 2x3 matrix.
+    /// let v = [0, 1, 2, 10, 11, 12];
+    /// assert_eq!(Ok(&12), v.eget_nd(&[1, 2], &[2, 3]));
+    /// assert_eq!(
+    ///     &ErrorType::DimensionOutOfBounds(1, 3, 3),
+    ///     v.eget_nd(&[1, 3], &[2, 3]).err().unwrap().ty()
+    /// );
+    /// ```
+    fn eget_nd(&self, indices: &[usize], shape: &[usize]) -> Result<&T>;
+}
+
+impl<T> GridExt<T> for [T] {
+    #[inline]
+    fn eget_nd(&self, indices: &[usize], shape: &[usize]) -> Result<&T> {
+        if indices.len() != shape.len() {
+            return Err(EzError::new(ErrorType::ShapeMismatch(
+                indices.len(),
+                shape.len(),
+            )))
+            .loc(flc!());
+        }
+
+        let mut offset = 0;
+        for (dim, (&index, &len)) in indices.iter().zip(shape).enumerate() {
+            if index >= len {
+                return Err(EzError::new(ErrorType::DimensionOutOfBounds(
+                    dim, index, len,
+                )))
+                .loc(flc!());
+            }
+            offset = offset * len + index;
+        }
+
+        self.eget(offset).loc(flc!())
+    }
+}
+
+/// Extension trait for two-dimensional data stored as nested slices, e.g.
+/// `[&[T]]` or `Vec<Vec<T>>`.
+pub trait NestedSliceExt<T> {
+    /// Returns a reference to the element at `col` of the row at `row`. The
+    /// row is checked first. Both checks return [`ErrorType::IndexOutOfBounds`]
+    /// with a context message that tells whether the row or the column was
+    /// out of bounds, and the frame of the failing check.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// let rows = vec![vec![0, 1, 2], vec![10, 11]];
+    /// assert_eq!(Ok(&11), rows.eget_2d(1, 1));
+    ///
+    /// let err = rows.eget_2d(1, 2).unwrap_err();
+    /// assert_eq!(&ErrorType::IndexOutOfBounds(2, 2), err.ty());
+    /// assert_eq!(&["column 2 of row 1 is out of bounds".to_owned()], err.contexts());
+    /// ```
+    fn eget_2d(&self, row: usize, col: usize) -> Result<&T>;
+}
+
+impl<T, R> NestedSliceExt<T> for [R]
+where
+    R: AsRef<[T]>,
+{
+    #[inline]
+    fn eget_2d(&self, row: usize, col: usize) -> Result<&T> {
+        let cols = match self.get(row) {
+            Some(cols) => cols.as_ref(),
+            None => {
+                return Err(EzError::new(ErrorType::IndexOutOfBounds(row, self.len()))
+                    .context(&format!("row {} is out of bounds", row)))
+                .loc(flc!());
+            }
+        };
+
+        match cols.get(col) {
+            Some(value) => Ok(value),
+            None => Err(EzError::new(ErrorType::IndexOutOfBounds(col, cols.len()))
+                .context(&format!("column {} of row {} is out of bounds", col, row)))
+            .loc(flc!()),
+        }
+    }
+}
+
 impl<T> SliceExt<usize, T> for [T] {
     #[inline]
     fn eget(&self, index: usize) -> Result<&T> {
@@ -63,9 +580,18 @@ impl<T> SliceExt<usize, T> for [T] {
             Err(EzError::new(ErrorType::IndexOutOfBounds(index, self.len()))).loc(flc!())
         }
     }
+
+    #[inline]
+    fn eget_opt(&self, index: usize) -> Option<&T> {
+        if index < self.len() {
+            Some(unsafe { self.get_unchecked(index) })
+        } else {
+            None
+        }
+    }
 }
 
-impl<'a, T> SliceExt<usize, T> for &'a [T] {
+impl<T> SliceExt<usize, T> for &[T] {
     #[inline]
     fn eget(&self, index: usize) -> Result<&T> {
         if index < self.len() {
@@ -74,6 +600,15 @@ impl<'a, T> SliceExt<usize, T> for &'a [T] {
             Err(EzError::new(ErrorType::IndexOutOfBounds(index, self.len()))).loc(flc!())
         }
     }
+
+    #[inline]
+    fn eget_opt(&self, index: usize) -> Option<&T> {
+        if index < self.len() {
+            Some(unsafe { self.get_unchecked(index) })
+        } else {
+            None
+        }
+    }
 }
 
 impl<T> SliceExtMut<usize, T> for [T] {
@@ -87,7 +622,7 @@ impl<T> SliceExtMut<usize, T> for [T] {
     }
 }
 
-impl<'a, T> SliceExtMut<usize, T> for &'a mut [T] {
+impl<T> SliceExtMut<usize, T> for &mut [T] {
     #[inline]
     fn eget_mut(&mut self, index: usize) -> Result<&mut T> {
         if index < self.len() {
@@ -114,9 +649,18 @@ impl<T> SliceExt<ops::Range<usize>, [T]> for [T] {
             Ok(unsafe { self.get_unchecked(index) })
         }
     }
+
+    #[inline]
+    fn eget_opt(&self, index: ops::Range<usize>) -> Option<&[T]> {
+        if index.start > index.end || index.start >= self.len() || index.end > self.len() {
+            None
+        } else {
+            Some(unsafe { self.get_unchecked(index) })
+        }
+    }
 }
 
-impl<'a, T> SliceExt<ops::Range<usize>, [T]> for &'a [T] {
+impl<T> SliceExt<ops::Range<usize>, [T]> for &[T] {
     #[inline]
     fn eget(&self, index: ops::Range<usize>) -> Result<&[T]> {
         if index.start > index.end {
@@ -132,6 +676,15 @@ impl<'a, T> SliceExt<ops::Range<usize>, [T]> for &'a [T] {
             Ok(unsafe { self.get_unchecked(index) })
         }
     }
+
+    #[inline]
+    fn eget_opt(&self, index: ops::Range<usize>) -> Option<&[T]> {
+        if index.start > index.end || index.start >= self.len() || index.end > self.len() {
+            None
+        } else {
+            Some(unsafe { self.get_unchecked(index) })
+        }
+    }
 }
 
 impl<T> SliceExtMut<ops::Range<usize>, [T]> for [T] {
@@ -152,7 +705,7 @@ impl<T> SliceExtMut<ops::Range<usize>, [T]> for [T] {
     }
 }
 
-impl<'a, T> SliceExtMut<ops::Range<usize>, [T]> for &'a mut [T] {
+impl<T> SliceExtMut<ops::Range<usize>, [T]> for &mut [T] {
     #[inline]
     fn eget_mut(&mut self, index: ops::Range<usize>) -> Result<&mut [T]> {
         if index.start > index.end {
@@ -175,13 +728,23 @@ impl<T> SliceExt<ops::RangeTo<usize>, [T]> for [T] {
     fn eget(&self, index: ops::RangeTo<usize>) -> Result<&[T]> {
         self.eget(0..index.end).loc(flc!())
     }
+
+    #[inline]
+    fn eget_opt(&self, index: ops::RangeTo<usize>) -> Option<&[T]> {
+        self.eget_opt(0..index.end)
+    }
 }
 
-impl<'a, T> SliceExt<ops::RangeTo<usize>, [T]> for &'a [T] {
+impl<T> SliceExt<ops::RangeTo<usize>, [T]> for &[T] {
     #[inline]
     fn eget(&self, index: ops::RangeTo<usize>) -> Result<&[T]> {
         self.eget(0..index.end).loc(flc!())
     }
+
+    #[inline]
+    fn eget_opt(&self, index: ops::RangeTo<usize>) -> Option<&[T]> {
+        self.eget_opt(0..index.end)
+    }
 }
 
 impl<T> SliceExtMut<ops::RangeTo<usize>, [T]> for [T] {
@@ -191,7 +754,7 @@ impl<T> SliceExtMut<ops::RangeTo<usize>, [T]> for [T] {
     }
 }
 
-impl<'a, T> SliceExtMut<ops::RangeTo<usize>, [T]> for &'a mut [T] {
+impl<T> SliceExtMut<ops::RangeTo<usize>, [T]> for &mut [T] {
     #[inline]
     fn eget_mut(&mut self, index: ops::RangeTo<usize>) -> Result<&mut [T]> {
         self.eget_mut(0..index.end).loc(flc!())
@@ -203,13 +766,23 @@ impl<T> SliceExt<ops::RangeFrom<usize>, [T]> for [T] {
     fn eget(&self, index: ops::RangeFrom<usize>) -> Result<&[T]> {
         self.eget(index.start..self.len()).loc(flc!())
     }
+
+    #[inline]
+    fn eget_opt(&self, index: ops::RangeFrom<usize>) -> Option<&[T]> {
+        self.eget_opt(index.start..self.len())
+    }
 }
 
-impl<'a, T> SliceExt<ops::RangeFrom<usize>, [T]> for &'a [T] {
+impl<T> SliceExt<ops::RangeFrom<usize>, [T]> for &[T] {
     #[inline]
     fn eget(&self, index: ops::RangeFrom<usize>) -> Result<&[T]> {
         self.eget(index.start..self.len()).loc(flc!())
     }
+
+    #[inline]
+    fn eget_opt(&self, index: ops::RangeFrom<usize>) -> Option<&[T]> {
+        self.eget_opt(index.start..self.len())
+    }
 }
 
 impl<T> SliceExtMut<ops::RangeFrom<usize>, [T]> for [T] {
@@ -219,7 +792,7 @@ impl<T> SliceExtMut<ops::RangeFrom<usize>, [T]> for [T] {
     }
 }
 
-impl<'a, T> SliceExtMut<ops::RangeFrom<usize>, [T]> for &'a mut [T] {
+impl<T> SliceExtMut<ops::RangeFrom<usize>, [T]> for &mut [T] {
     #[inline]
     fn eget_mut(&mut self, index: ops::RangeFrom<usize>) -> Result<&mut [T]> {
         self.eget_mut(index.start..self.len()).loc(flc!())
@@ -231,13 +804,23 @@ impl<T> SliceExt<ops::RangeFull, [T]> for [T] {
     fn eget(&self, _: ops::RangeFull) -> Result<&[T]> {
         Ok(self)
     }
+
+    #[inline]
+    fn eget_opt(&self, _: ops::RangeFull) -> Option<&[T]> {
+        Some(self)
+    }
 }
 
-impl<'a, T> SliceExt<ops::RangeFull, [T]> for &'a [T] {
+impl<T> SliceExt<ops::RangeFull, [T]> for &[T] {
     #[inline]
     fn eget(&self, _: ops::RangeFull) -> Result<&[T]> {
         Ok(self)
     }
+
+    #[inline]
+    fn eget_opt(&self, _: ops::RangeFull) -> Option<&[T]> {
+        Some(self)
+    }
 }
 
 impl<T> SliceExtMut<ops::RangeFull, [T]> for [T] {
@@ -247,7 +830,7 @@ impl<T> SliceExtMut<ops::RangeFull, [T]> for [T] {
     }
 }
 
-impl<'a, T> SliceExtMut<ops::RangeFull, [T]> for &'a mut [T] {
+impl<T> SliceExtMut<ops::RangeFull, [T]> for &mut [T] {
     #[inline]
     fn eget_mut(&mut self, _: ops::RangeFull) -> Result<&mut [T]> {
         Ok(self)
@@ -263,9 +846,18 @@ impl<T> SliceExt<ops::RangeInclusive<usize>, [T]> for [T] {
             self.eget(*index.start()..(*index.end() + 1))
         }
     }
+
+    #[inline]
+    fn eget_opt(&self, index: ops::RangeInclusive<usize>) -> Option<&[T]> {
+        if *index.end() == usize::MAX {
+            None
+        } else {
+            self.eget_opt(*index.start()..(*index.end() + 1))
+        }
+    }
 }
 
-impl<'a, T> SliceExt<ops::RangeInclusive<usize>, [T]> for &'a [T] {
+impl<T> SliceExt<ops::RangeInclusive<usize>, [T]> for &[T] {
     #[inline]
     fn eget(&self, index: ops::RangeInclusive<usize>) -> Result<&[T]> {
         if *index.end() == usize::MAX {
@@ -274,6 +866,15 @@ impl<'a, T> SliceExt<ops::RangeInclusive<usize>, [T]> for &'a [T] {
             self.eget(*index.start()..(*index.end() + 1))
         }
     }
+
+    #[inline]
+    fn eget_opt(&self, index: ops::RangeInclusive<usize>) -> Option<&[T]> {
+        if *index.end() == usize::MAX {
+            None
+        } else {
+            self.eget_opt(*index.start()..(*index.end() + 1))
+        }
+    }
 }
 
 impl<T> SliceExtMut<ops::RangeInclusive<usize>, [T]> for [T] {
@@ -287,7 +888,7 @@ impl<T> SliceExtMut<ops::RangeInclusive<usize>, [T]> for [T] {
     }
 }
 
-impl<'a, T> SliceExtMut<ops::RangeInclusive<usize>, [T]> for &'a mut [T] {
+impl<T> SliceExtMut<ops::RangeInclusive<usize>, [T]> for &mut [T] {
     #[inline]
     fn eget_mut(&mut self, index: ops::RangeInclusive<usize>) -> Result<&mut [T]> {
         if *index.end() == usize::MAX {
@@ -303,13 +904,23 @@ impl<T> SliceExt<ops::RangeToInclusive<usize>, [T]> for [T] {
     fn eget(&self, index: ops::RangeToInclusive<usize>) -> Result<&[T]> {
         self.eget(0..=index.end).loc(flc!())
     }
+
+    #[inline]
+    fn eget_opt(&self, index: ops::RangeToInclusive<usize>) -> Option<&[T]> {
+        self.eget_opt(0..=index.end)
+    }
 }
 
-impl<'a, T> SliceExt<ops::RangeToInclusive<usize>, [T]> for &'a [T] {
+impl<T> SliceExt<ops::RangeToInclusive<usize>, [T]> for &[T] {
     #[inline]
     fn eget(&self, index: ops::RangeToInclusive<usize>) -> Result<&[T]> {
         self.eget(0..=index.end).loc(flc!())
     }
+
+    #[inline]
+    fn eget_opt(&self, index: ops::RangeToInclusive<usize>) -> Option<&[T]> {
+        self.eget_opt(0..=index.end)
+    }
 }
 
 impl<T> SliceExtMut<ops::RangeToInclusive<usize>, [T]> for [T] {
@@ -319,14 +930,40 @@ impl<T> SliceExtMut<ops::RangeToInclusive<usize>, [T]> for [T] {
     }
 }
 
-impl<'a, T> SliceExtMut<ops::RangeToInclusive<usize>, [T]> for &'a mut [T] {
+impl<T> SliceExtMut<ops::RangeToInclusive<usize>, [T]> for &mut [T] {
     #[inline]
     fn eget_mut(&mut self, index: ops::RangeToInclusive<usize>) -> Result<&mut [T]> {
         self.eget_mut(0..=index.end).loc(flc!())
     }
 }
 
-#[cfg(test)]
+impl<T> SliceExt<usize, T> for VecDeque<T> {
+    #[inline]
+    fn eget(&self, index: usize) -> Result<&T> {
+        match self.get(index) {
+            Some(value) => Ok(value),
+            None => Err(EzError::new(ErrorType::IndexOutOfBounds(index, self.len()))).loc(flc!()),
+        }
+    }
+
+    #[inline]
+    fn eget_opt(&self, index: usize) -> Option<&T> {
+        self.get(index)
+    }
+}
+
+impl<T> SliceExtMut<usize, T> for VecDeque<T> {
+    #[inline]
+    fn eget_mut(&mut self, index: usize) -> Result<&mut T> {
+        let len = self.len();
+        match self.get_mut(index) {
+            Some(value) => Ok(value),
+            None => Err(EzError::new(ErrorType::IndexOutOfBounds(index, len))).loc(flc!()),
+        }
+    }
+}
+
+#[cfg(all(test, not(feature = "panic_on_error")))]
 mod tests {
     use super::*;
 
@@ -521,4 +1158,327 @@ mod tests {
         assert_eq!(Ok(&mut clone[..=2]), arr.eget_mut(..=2));
         assert!(arr.eget_mut(..=3).is_err());
     }
+
+    #[test]
+    fn split_first() {
+        let arr = [6, 12, 5];
+        let empty: &[i32] = &[];
+
+        assert_eq!(Ok((&6, &arr[1..])), arr.esplit_first());
+        assert_eq!(
+            &ErrorType::EmptySlice,
+            empty.esplit_first().err().unwrap().ty()
+        );
+    }
+
+    #[test]
+    fn first_and_last() {
+        let mut arr = [6, 12, 5];
+        let mut empty: [i32; 0] = [];
+
+        assert_eq!(Ok(&6), arr.efirst());
+        assert_eq!(Ok(&5), arr.elast());
+        *arr.efirst_mut().unwrap() = 1;
+        *arr.elast_mut().unwrap() = 9;
+        assert_eq!([1, 12, 9], arr);
+
+        for err in [
+            empty.efirst().unwrap_err(),
+            empty.elast().unwrap_err(),
+            empty.efirst_mut().unwrap_err(),
+            empty.elast_mut().unwrap_err(),
+        ] {
+            assert_eq!(&ErrorType::EmptySlice, err.ty());
+            assert_eq!(1, err.frames().len());
+        }
+    }
+
+    #[test]
+    fn split_last() {
+        let arr = [6, 12, 5];
+        let empty: &[i32] = &[];
+
+        assert_eq!(Ok((&5, &arr[..2])), arr.esplit_last());
+        assert_eq!(
+            &ErrorType::EmptySlice,
+            empty.esplit_last().err().unwrap().ty()
+        );
+    }
+
+    #[cfg(not(feature = "single_frame"))]
+    #[test]
+    fn result_chain() {
+        fn producer(fail: bool) -> Result<&'static [i32]> {
+            if fail {
+                Err(EzError::message("failed")).loc(flc!())
+            } else {
+                Ok(&[6, 12, 5])
+            }
+        }
+
+        assert_eq!(Ok(&12), producer(false).eget(1));
+        assert_eq!(
+            &ErrorType::IndexOutOfBounds(3, 3),
+            producer(false).eget(3).err().unwrap().ty()
+        );
+
+        let err = producer(true).eget(1).err().unwrap();
+        assert_eq!(&ErrorType::Message("failed".into()), err.ty());
+        assert_eq!(2, err.frames().len());
+    }
+
+    #[test]
+    fn result_chain_vec() {
+        let ok: Result<Vec<i32>> = Ok(vec![6, 12, 5]);
+        let oob: Result<Vec<i32>> = Ok(vec![6, 12, 5]);
+
+        assert_eq!(Ok(5), ok.eget(2));
+        assert_eq!(
+            &ErrorType::IndexOutOfBounds(3, 3),
+            oob.eget(3).err().unwrap().ty()
+        );
+    }
+
+    #[test]
+    fn grid_nd() {
+        // A 2x3x4 tensor where every element encodes its own position.
+        let data: Vec<usize> = (0..24).collect();
+        let shape = [2, 3, 4];
+
+        assert_eq!(Ok(&0), data.eget_nd(&[0, 0, 0], &shape));
+        assert_eq!(Ok(&23), data.eget_nd(&[1, 2, 3], &shape));
+        assert_eq!(Ok(&(12 + 4 + 3)), data.eget_nd(&[1, 1, 3], &shape));
+    }
+
+    #[test]
+    fn grid_nd_errors() {
+        let data: Vec<usize> = (0..24).collect();
+        let shape = [2, 3, 4];
+
+        let err = data.eget_nd(&[1, 3, 0], &shape).err().unwrap();
+        assert_eq!(&ErrorType::DimensionOutOfBounds(1, 3, 3), err.ty());
+        assert_eq!(1, err.frames().len());
+
+        let err = data.eget_nd(&[1, 0], &shape).err().unwrap();
+        assert_eq!(&ErrorType::ShapeMismatch(2, 3), err.ty());
+
+        let err = data.eget_nd(&[1, 2, 3], &[2, 3, 5]).err().unwrap();
+        assert_eq!(&ErrorType::IndexOutOfBounds(28, 24), err.ty());
+    }
+
+    #[test]
+    fn zip_equal_lengths() {
+        let a = [1, 2, 3];
+        let b: Vec<char> = "abc".chars().collect();
+        let c: &[&str] = &["x", "y", "z"];
+
+        assert_eq!(Ok((&1, &'a', &"x")), zip_eget!(0, a, b, c));
+        assert_eq!(Ok((&3, &'c', &"z")), zip_eget!(2, a, b, c));
+        assert!(zip_eget!(3, a, b, c).is_err());
+    }
+
+    #[test]
+    fn zip_unequal_lengths() {
+        let a = [1, 2, 3];
+        let b: Vec<char> = "ab".chars().collect();
+        let c: &[&str] = &["x"];
+
+        assert_eq!(Ok((&1, &'a', &"x")), zip_eget!(0, a, b, c));
+
+        let err = zip_eget!(1, a, b, c).err().unwrap();
+        assert_eq!(&ErrorType::IndexOutOfBounds(1, 1), err.ty());
+        assert_eq!(&["in slice 2 of zip_eget!".to_owned()][..], err.contexts());
+
+        let err = zip_eget!(2, a, b, c).err().unwrap();
+        assert_eq!(&ErrorType::IndexOutOfBounds(2, 2), err.ty());
+        assert_eq!(&["in slice 1 of zip_eget!".to_owned()][..], err.contexts());
+    }
+
+    #[test]
+    fn chunks_and_windows() {
+        let arr = [6, 12, 5, 7, 1];
+
+        let chunks: Vec<&[i32]> = arr.echunks(2).unwrap().collect();
+        assert_eq!(vec![&[6, 12][..], &[5, 7], &[1]], chunks);
+        let windows: Vec<&[i32]> = arr.ewindows(4).unwrap().collect();
+        assert_eq!(vec![&[6, 12, 5, 7][..], &[12, 5, 7, 1]], windows);
+        assert_eq!(0, arr.ewindows(6).unwrap().count());
+
+        let err = arr.echunks(0).unwrap_err();
+        assert_eq!(&ErrorType::InvalidRange, err.ty());
+        assert_eq!(&["chunk size must not be zero".to_owned()], err.contexts());
+        assert_eq!(1, err.frames().len());
+
+        let err = arr.ewindows(0).unwrap_err();
+        assert_eq!(&ErrorType::InvalidRange, err.ty());
+        assert_eq!(&["window size must not be zero".to_owned()], err.contexts());
+    }
+
+    #[cfg(not(feature = "single_frame"))]
+    #[test]
+    fn try_map() {
+        let arr = [6, 12, 5, 8];
+        let half = |v: &i32| {
+            if v % 2 == 0 {
+                Ok(v / 2)
+            } else {
+                Err(EzError::message("odd")).loc(flc!())
+            }
+        };
+
+        assert_eq!(Ok(vec![3, 6]), arr[..2].etry_map(half));
+
+        let err = arr.etry_map(half).err().unwrap();
+        assert_eq!(&ErrorType::Message("odd".into()), err.ty());
+        assert_eq!(
+            &["while mapping the element at index 2".to_owned()][..],
+            err.contexts()
+        );
+        assert_eq!(2, err.frames().len());
+    }
+
+    #[test]
+    fn get_status() {
+        let empty: &[u8] = &[];
+        let res = empty.eget_status(0);
+        assert_eq!(&ErrorType::EmptySlice, res.err().unwrap().ty());
+
+        let v = [1u8, 2, 3];
+        assert_eq!(Ok(&3), v.eget_status(2));
+        let res = v.eget_status(5);
+        assert_eq!(&ErrorType::IndexOutOfBounds(5, 3), res.err().unwrap().ty());
+    }
+
+    #[test]
+    fn eget_opt_matches_eget() {
+        let arr: &[i32] = &[6, 12, 5];
+
+        for i in 0..5 {
+            assert_eq!(arr.eget(i).ok(), arr.eget_opt(i));
+            for j in 0..5 {
+                assert_eq!(arr.eget(i..j).ok(), arr.eget_opt(i..j));
+                assert_eq!(arr.eget(i..=j).ok(), arr.eget_opt(i..=j));
+            }
+            assert_eq!(arr.eget(..i).ok(), arr.eget_opt(..i));
+            assert_eq!(arr.eget(i..).ok(), arr.eget_opt(i..));
+            assert_eq!(arr.eget(..=i).ok(), arr.eget_opt(..=i));
+        }
+        assert_eq!(Some(arr), arr.eget_opt(..));
+        assert_eq!(None, arr.eget_opt(0..=usize::MAX));
+    }
+
+    /// Only implements the required method of [`SliceExt`].
+    struct OnlyEget(Vec<u8>);
+
+    impl SliceExt<usize, u8> for OnlyEget {
+        fn eget(&self, index: usize) -> Result<&u8> {
+            self.0.eget(index)
+        }
+    }
+
+    #[test]
+    fn eget_opt_default() {
+        let v = OnlyEget(Vec::from([4, 2]));
+        assert_eq!(Some(&2), v.eget_opt(1));
+        assert_eq!(None, v.eget_opt(2));
+    }
+
+    #[test]
+    fn const_index() {
+        let arr = [6, 12, 5];
+        assert_eq!(Ok(&6), arr.eget_const::<0>());
+        assert_eq!(Ok(&5), arr.eget_const::<2>());
+        assert_eq!(arr.eget(2), arr.eget_const::<2>());
+
+        let err = arr.eget_const::<3>().unwrap_err();
+        assert_eq!(&ErrorType::IndexOutOfBounds(3, 3), err.ty());
+        assert_eq!(1, err.frames().len());
+
+        let slice: &[i32] = &arr[1..];
+        assert_eq!(Ok(&5), slice.eget_const::<1>());
+        assert_eq!(
+            &ErrorType::IndexOutOfBounds(2, 2),
+            slice.eget_const::<2>().unwrap_err().ty()
+        );
+        assert_eq!(Ok(&12), Vec::from([6, 12]).eget_const::<1>());
+    }
+
+    #[test]
+    fn copied_and_cloned() {
+        let arr = [6, 12, 5];
+        assert_eq!(Ok(17), arr.eget_copied(1).map(|v| v + 5));
+
+        let err = arr.eget_copied(3).unwrap_err();
+        assert_eq!(&ErrorType::IndexOutOfBounds(3, 3), err.ty());
+        assert_eq!(1, err.frames().len());
+
+        let first = arr.eget(1..3).and_then(|rest| rest.eget_copied(0));
+        assert_eq!(Ok(12), first);
+        let err = arr
+            .eget(2..3)
+            .and_then(|rest| rest.eget_copied(1))
+            .unwrap_err();
+        assert_eq!(&ErrorType::IndexOutOfBounds(1, 1), err.ty());
+
+        let names = [String::from("a"), String::from("b")];
+        assert_eq!(Ok(String::from("b")), names.eget_cloned(1));
+        assert!(names.eget_cloned(2).is_err());
+    }
+
+    #[test]
+    fn vec_deque_index() {
+        let mut deque = VecDeque::from([12, 5]);
+        deque.push_front(6);
+
+        assert_eq!(Ok(&6), deque.eget(0));
+        assert_eq!(Ok(&12), deque.eget(1));
+        assert_eq!(Ok(&5), deque.eget(2));
+        assert_eq!(Some(&5), deque.eget_opt(2));
+        assert_eq!(None, deque.eget_opt(3));
+
+        let err = deque.eget(3).unwrap_err();
+        assert_eq!(&ErrorType::IndexOutOfBounds(3, 3), err.ty());
+        assert_eq!(1, err.frames().len());
+    }
+
+    #[test]
+    fn vec_deque_index_mut() {
+        let mut deque = VecDeque::from([6, 12, 5]);
+
+        if let Ok(elem) = deque.eget_mut(1) {
+            *elem = 42;
+        }
+        assert_eq!(VecDeque::from([6, 42, 5]), deque);
+        assert_eq!(
+            &ErrorType::IndexOutOfBounds(3, 3),
+            deque.eget_mut(3).unwrap_err().ty()
+        );
+    }
+
+    #[test]
+    fn nested_row_out_of_bounds() {
+        let rows: &[&[i32]] = &[&[6, 12], &[5]];
+
+        assert_eq!(Ok(&12), rows.eget_2d(0, 1));
+        let err = rows.eget_2d(2, 0).unwrap_err();
+        assert_eq!(&ErrorType::IndexOutOfBounds(2, 2), err.ty());
+        assert_eq!(&["row 2 is out of bounds".to_owned()], err.contexts());
+        assert_eq!(1, err.frames().len());
+    }
+
+    #[test]
+    fn nested_column_out_of_bounds() {
+        let rows = Vec::from([vec![6, 12], vec![5]]);
+
+        assert_eq!(Ok(&5), rows.eget_2d(1, 0));
+        let err = rows.eget_2d(1, 1).unwrap_err();
+        assert_eq!(&ErrorType::IndexOutOfBounds(1, 1), err.ty());
+        assert_eq!(
+            &["column 1 of row 1 is out of bounds".to_owned()],
+            err.contexts()
+        );
+
+        let row_err = rows.eget_2d(2, 0).unwrap_err();
+        assert_ne!(row_err.frames()[0].line, err.frames()[0].line);
+    }
 }
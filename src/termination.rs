@@ -0,0 +1,113 @@
+//! Support for returning errors from `main`.
+
+use crate::core::*;
+use std::process::{ExitCode, Termination};
+
+/// Wraps the result of `main` so that an error is printed with the full
+/// stacktrace (like [`Handle::handle`]) instead of its [`Debug`] output.
+///
+/// The exit code is chosen by [`EzError::exit_code`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # use ez_err::prelude::*;
+/// fn run() -> Result<()> {
+///     bail!("nothing to do")
+/// }
+///
+/// fn main() -> ErrReport {
+///     ErrReport(run())
+/// }
+/// ```
+///
+/// [`Debug`]: std::fmt::Debug
+#[derive(Debug)]
+pub struct ErrReport(pub Result<()>);
+
+impl From<Result<()>> for ErrReport {
+    fn from(res: Result<()>) -> Self {
+        ErrReport(res)
+    }
+}
+
+impl Termination for ErrReport {
+    fn report(self) -> ExitCode {
+        self.0.handle_exit()
+    }
+}
+
+impl EzError {
+    /// Returns the process exit code for the error, e.g. for a `main` that
+    /// returns [`ExitCode`]. This is the [`EzError::code`] of the error if it
+    /// is in the range `1..=255` and `1` for all other errors, so an error
+    /// never maps to a successful exit code.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ez_err::prelude::*;
+    /// # use std::process::ExitCode;
+    /// let err = EzError::message("config missing").with_code(3);
+    /// assert_eq!(format!("{:?}", ExitCode::from(3)), format!("{:?}", err.exit_code()));
+    /// ```
+    pub fn exit_code(&self) -> ExitCode {
+        ExitCode::from(exit_code_value(self))
+    }
+}
+
+/// Returns the numeric process exit code for the error.
+fn exit_code_value(err: &EzError) -> u8 {
+    match err.code() {
+        Some(code) if (1..=255).contains(&code) => code as u8,
+        _ => 1,
+    }
+}
+
+#[cfg(all(test, not(feature = "panic_on_error")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exit_codes() {
+        assert_eq!(1, exit_code_value(&EzError::message("failed")));
+        assert_eq!(1, exit_code_value(&EzError::new(ErrorType::NoneOption)));
+
+        let err = EzError::custom(3, "Config".into(), "missing key".into());
+        assert_eq!(3, exit_code_value(&err));
+        let err = EzError::custom(404, "NotFound".into(), "no such file".into());
+        assert_eq!(1, exit_code_value(&err));
+        assert_eq!(7, exit_code_value(&EzError::message("failed").with_code(7)));
+        assert_eq!(1, exit_code_value(&EzError::message("failed").with_code(0)));
+    }
+
+    #[test]
+    fn exit_code() {
+        let err = EzError::custom(3, "Config".into(), "missing key".into());
+        assert_eq!(
+            format!("{:?}", ExitCode::from(3)),
+            format!("{:?}", err.exit_code())
+        );
+
+        let res: Result<()> = Err(err);
+        assert_eq!(
+            format!("{:?}", ExitCode::from(3)),
+            format!("{:?}", res.handle_exit())
+        );
+        assert_eq!(
+            format!("{:?}", ExitCode::SUCCESS),
+            format!("{:?}", Ok(()).handle_exit())
+        );
+    }
+
+    #[test]
+    fn report_renders_error() {
+        let err = EzError::custom(3, "Config".into(), "missing key".into());
+        assert!(err
+            .render()
+            .starts_with("Error Config: missing key\n\nStacktrace:\n"));
+
+        let _ = ErrReport(Ok(())).report();
+        let _ = ErrReport::from(Err(err)).report();
+    }
+}
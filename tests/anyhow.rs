@@ -0,0 +1,28 @@
+#![cfg(all(
+    feature = "anyhow",
+    not(feature = "no_stacktrace"),
+    not(feature = "panic_on_error")
+))]
+
+use ez_err::prelude::*;
+
+fn load() -> Result<u32> {
+    Err(EzError::message("config missing")).loc(flc!())
+}
+
+fn app() -> anyhow::Result<u32> {
+    let value = load().loc(flc!())?;
+    Ok(value)
+}
+
+#[test]
+fn into_anyhow() {
+    let err = app().unwrap_err();
+
+    let text = err.to_string();
+    assert!(text.starts_with("Error Message: config missing\n"));
+    assert_eq!(2, text.matches("tests/anyhow.rs:").count());
+
+    let ez = err.downcast::<EzError>().unwrap();
+    assert_eq!(&ErrorType::Message("config missing".into()), ez.ty());
+}
@@ -0,0 +1,28 @@
+#![cfg(all(feature = "std", not(feature = "panic_on_error")))]
+
+use ez_err::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static HANDLED: AtomicUsize = AtomicUsize::new(0);
+
+fn count_handler(err: &EzError) {
+    assert_eq!("Message", err.ty().name());
+    HANDLED.fetch_add(1, Ordering::SeqCst);
+}
+
+#[test]
+fn global_handler() {
+    set_global_handler(Some(count_handler));
+
+    let res: Result<()> = Err(EzError::message("counted")).loc(flc!());
+    assert_eq!(None, res.handle());
+    assert_eq!(1, HANDLED.load(Ordering::SeqCst));
+
+    let mut err = EzError::message("reported");
+    err.report();
+    assert_eq!(2, HANDLED.load(Ordering::SeqCst));
+
+    set_global_handler(None);
+    Err::<(), _>(EzError::message("printed")).handle();
+    assert_eq!(2, HANDLED.load(Ordering::SeqCst));
+}
@@ -0,0 +1,27 @@
+#![cfg(all(feature = "std", not(feature = "panic_on_error")))]
+
+use ez_err::prelude::*;
+use std::sync::Mutex;
+
+static HANDLED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn record_handler(err: &EzError) {
+    HANDLED.lock().unwrap().push(err.ty().to_string());
+}
+
+#[test]
+fn handle_all() {
+    set_global_handler(Some(record_handler));
+
+    let results = vec![
+        Ok(1),
+        Err(EzError::message("first")).loc(flc!()),
+        Ok(2),
+        Err(EzError::message("second")).loc(flc!()),
+        Ok(3),
+    ];
+    assert_eq!(vec![1, 2, 3], results.handle_all());
+    assert_eq!(vec!["first", "second"], *HANDLED.lock().unwrap());
+
+    set_global_handler(None);
+}
@@ -0,0 +1,35 @@
+#![cfg(all(
+    feature = "std",
+    not(any(
+        feature = "no_stacktrace",
+        feature = "single_frame",
+        feature = "panic_on_error"
+    ))
+))]
+
+use ez_err::prelude::*;
+
+const EXTERNAL: ConstLocation =
+    ConstLocation::new("/home/user/.cargo/registry/src/dep-1.0.0/src/lib.rs", 10, 5);
+const INSIDE_ROOT: ConstLocation =
+    ConstLocation::new(concat!(env!("CARGO_MANIFEST_DIR"), "/src/lib.rs"), 20, 5);
+
+#[test]
+fn local_frames_only() {
+    local_frames_only!();
+
+    let mut err = EzError::message("filtered");
+    err.add_frame(&EXTERNAL);
+    err.add_frame(&INSIDE_ROOT);
+    let err = Err::<(), _>(err).loc(flc!()).err().unwrap();
+
+    assert_eq!(2, err.frames().len());
+    assert_eq!(&INSIDE_ROOT, err.frames()[0]);
+    assert_eq!(file!(), err.frames()[1].file);
+
+    set_local_root(None);
+
+    let mut err = EzError::message("unfiltered");
+    err.add_frame(&EXTERNAL);
+    assert_eq!(1, err.frames().len());
+}
@@ -0,0 +1,40 @@
+#![cfg(not(any(
+    feature = "no_stacktrace",
+    feature = "single_frame",
+    feature = "panic_on_error"
+)))]
+
+use ez_err::prelude::*;
+
+fn recurse(depth: u32) -> Result<()> {
+    if depth == 0 {
+        return Err(EzError::message("too deep")).loc(flc!());
+    }
+    recurse(depth - 1).loc(flc!())
+}
+
+#[test]
+fn truncates_frames() {
+    set_max_frames(Some(3));
+    let err = recurse(9).unwrap_err();
+    let merged = recurse(1).unwrap_err().with(recurse(3).unwrap_err());
+    set_max_frames(None);
+
+    assert_eq!(3, err.frames().len());
+    assert_eq!(11, err.frames()[0].line);
+    assert_eq!(7, err.truncated_frames());
+
+    let trace = err.trace_string();
+    assert_eq!(4, trace.lines().count());
+    assert!(trace.ends_with("... 7 frames truncated\n"));
+    assert!(err.debug_report().contains("... 7 frames truncated\n"));
+
+    // 2 + 3 frames are merged, the other error already dropped one.
+    assert_eq!(3, merged.frames().len());
+    assert_eq!(3, merged.truncated_frames());
+
+    let err = recurse(9).unwrap_err();
+    assert_eq!(10, err.frames().len());
+    assert_eq!(0, err.truncated_frames());
+    assert!(!err.trace_string().contains("truncated"));
+}
@@ -0,0 +1,18 @@
+#![cfg(all(feature = "std", not(feature = "panic_on_error")))]
+
+use ez_err::prelude::*;
+
+#[test]
+fn none_message() {
+    let err = None::<u32>.loc(flc!()).unwrap_err();
+    assert_eq!("Option was none", err.ty().to_string());
+
+    set_none_message(Some("required value missing"));
+    assert_eq!("required value missing", err.ty().to_string());
+    assert!(err
+        .render()
+        .starts_with("Error NoneOption: required value missing\n"));
+
+    set_none_message(None);
+    assert_eq!("Option was none", err.ty().to_string());
+}
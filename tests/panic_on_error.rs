@@ -0,0 +1,19 @@
+#![cfg(all(feature = "panic_on_error", debug_assertions))]
+// The other tests create errors and are skipped with this feature. The doc
+// tests can't be skipped, so only run them without it:
+// `cargo test --features panic_on_error --lib --tests`.
+
+use ez_err::prelude::*;
+
+#[test]
+#[should_panic(expected = "Message error created with `panic_on_error` enabled: disk full")]
+fn panics_on_creation() {
+    let _ = EzError::message("disk full");
+}
+
+#[test]
+#[should_panic(expected = "NoneOption error created with `panic_on_error` enabled")]
+fn panics_at_origin() {
+    let value: Option<u32> = None;
+    let _ = value.loc(flc!());
+}
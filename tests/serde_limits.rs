@@ -0,0 +1,35 @@
+#![cfg(all(
+    feature = "serde",
+    not(feature = "no_stacktrace"),
+    not(feature = "panic_on_error")
+))]
+
+use ez_err::prelude::*;
+
+fn location(file: &str, line: u32) -> &'static ConstLocation {
+    let json = format!(r#"{{"file":"{}","line":{},"column":1}}"#, file, line);
+    serde_json::from_str(&json).unwrap()
+}
+
+#[test]
+fn interning_is_limited() {
+    let first = location("src/main.rs", 1);
+    assert_eq!("src/main.rs", first.file);
+
+    // Long file names use up the bytes that may be interned.
+    let long = "x".repeat(64 * 1024);
+    let mut last = first;
+    for i in 0..20 {
+        last = location(&format!("{}{}", long, i), 1);
+    }
+    assert_eq!(&ConstLocation::new("<unknown>", 1, 1), last);
+
+    // Many distinct locations use up the number of locations.
+    for line in 0..20_000 {
+        last = location("src/main.rs", line);
+    }
+    assert_eq!(&ConstLocation::new("<unknown>", 0, 0), last);
+
+    // Locations that were interned before a limit was reached are reused.
+    assert!(std::ptr::eq(first, location("src/main.rs", 1)));
+}
@@ -0,0 +1,56 @@
+#![cfg(all(
+    feature = "single_frame",
+    not(feature = "no_stacktrace"),
+    not(feature = "panic_on_error")
+))]
+
+use ez_err::prelude::*;
+
+fn origin() -> Result<()> {
+    Err(EzError::message("failed")).loc(flc!())
+}
+
+fn middle() -> Result<()> {
+    origin().loc(flc!())
+}
+
+fn outer() -> Result<()> {
+    middle().loc(flc!())
+}
+
+#[test]
+fn keeps_first_frame() {
+    let err = outer().unwrap_err();
+
+    assert_eq!(1, err.frames().len());
+    assert_eq!(10, err.frames()[0].line);
+}
+
+#[test]
+fn ignores_tagged_frames() {
+    const FIRST: ConstLocation = ConstLocation::new("src/first.rs", 1, 1);
+    const SECOND: ConstLocation = ConstLocation::new("src/second.rs", 2, 2);
+
+    let mut err = EzError::message("failed");
+    err.add_frame(&FIRST);
+    err.add_tagged_frame(&SECOND, 4);
+    err.add_frame(&SECOND);
+
+    assert_eq!(vec![&FIRST], err.frames());
+    assert_eq!("src/first.rs:1:1\n", err.trace_string());
+}
+
+#[test]
+fn ignores_merged_frames() {
+    const FIRST: ConstLocation = ConstLocation::new("src/first.rs", 1, 1);
+    const SECOND: ConstLocation = ConstLocation::new("src/second.rs", 2, 2);
+
+    let mut err = EzError::message("failed");
+    err.add_frame(&FIRST);
+    let mut other = EzError::message("failed");
+    other.add_tagged_frame(&SECOND, 4);
+
+    let err = err.with(other);
+    assert_eq!(vec![&FIRST], err.frames());
+    assert_eq!("src/first.rs:1:1\n", err.trace_string());
+}
@@ -0,0 +1,25 @@
+#![cfg(all(feature = "std_backtrace", not(feature = "panic_on_error")))]
+
+use ez_err::prelude::*;
+
+fn read_config() -> Result<String> {
+    std::fs::read_to_string("/does/not/exist").loc(flc!())
+}
+
+#[test]
+fn foreign_errors_capture_backtrace() {
+    let err = read_config().unwrap_err();
+    assert!(err.backtrace().is_some());
+
+    let output = err.render();
+    let frames = output.find("Stacktrace:").unwrap();
+    let backtrace = output.find("OS backtrace:").unwrap();
+    assert!(frames < backtrace);
+}
+
+#[test]
+fn own_errors_have_no_backtrace() {
+    let err = EzError::message("own");
+    assert!(err.backtrace().is_none());
+    assert!(!err.render().contains("OS backtrace:"));
+}
@@ -0,0 +1,31 @@
+#![cfg(all(
+    feature = "std",
+    not(any(feature = "log", feature = "tracing", feature = "panic_on_error"))
+))]
+
+use ez_err::prelude::*;
+use std::process::Command;
+
+const CHILD_ENV: &str = "EZ_ERR_STDERR_CHILD";
+
+#[test]
+fn handle_prints_to_stderr() {
+    if std::env::var_os(CHILD_ENV).is_some() {
+        let res: Result<()> = Err(EzError::message("sent to stderr")).loc(flc!());
+        res.handle();
+        return;
+    }
+
+    // Run this test again in a child process to capture its real output.
+    let output = Command::new(std::env::current_exe().unwrap())
+        .args(["handle_prints_to_stderr", "--exact", "--nocapture"])
+        .env(CHILD_ENV, "1")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Error Message: sent to stderr\n"));
+    assert!(!stdout.contains("sent to stderr"));
+}
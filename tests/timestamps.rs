@@ -0,0 +1,53 @@
+#![cfg(all(
+    feature = "timestamps",
+    not(feature = "no_stacktrace"),
+    not(feature = "panic_on_error")
+))]
+
+use ez_err::prelude::*;
+use std::time::Duration;
+
+const INNER: ConstLocation = ConstLocation::new("src/inner.rs", 3, 9);
+const OUTER: ConstLocation = ConstLocation::new("src/outer.rs", 12, 5);
+
+#[test]
+fn elapsed_time_per_frame() {
+    let mut err = EzError::message("slow");
+    err.add_frame(&INNER);
+    std::thread::sleep(Duration::from_millis(5));
+    err.add_frame(&OUTER);
+
+    let times = err.frame_times();
+    assert_eq!(2, times.len());
+    assert!(times[1].duration_since(times[0]) >= Duration::from_millis(5));
+
+    let trace = err.trace_string();
+    let lines: Vec<&str> = trace.lines().collect();
+    assert_eq!("src/inner.rs:3:9", lines[0]);
+    assert!(lines[1].starts_with("src/outer.rs:12:5 (+"));
+}
+
+#[test]
+fn timestamps_are_ignored_by_eq() {
+    let build = || EzError::builder().frame(&INNER).frame(&OUTER).build();
+    let first = build();
+    std::thread::sleep(Duration::from_millis(1));
+
+    assert_eq!(first, build());
+}
+
+#[test]
+fn prepended_frame_keeps_times_in_sync() {
+    let mut err = EzError::message("merged");
+    err.add_frame(&OUTER);
+    std::thread::sleep(Duration::from_millis(5));
+    err.prepend_frame(&INNER);
+
+    let times = err.frame_times();
+    assert_eq!(2, times.len());
+    // The prepended origin takes over the time of the former first frame.
+    assert_eq!(times[1], times[0]);
+    assert!(err
+        .trace_string()
+        .starts_with("src/inner.rs:3:9\nsrc/outer.rs:12:5 (+"));
+}
@@ -0,0 +1,96 @@
+#![cfg(all(feature = "tracing", not(feature = "panic_on_error")))]
+
+use ez_err::prelude::*;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+/// Collects the fields of all events.
+#[derive(Clone, Default)]
+struct Collector(Arc<Mutex<Vec<(String, String)>>>);
+
+impl Visit for Collector {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        let value = format!("{:?}", value);
+        self.0
+            .lock()
+            .unwrap()
+            .push((field.name().to_owned(), value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        let value = value.to_owned();
+        self.0
+            .lock()
+            .unwrap()
+            .push((field.name().to_owned(), value));
+    }
+}
+
+impl Subscriber for Collector {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        event.record(&mut self.clone());
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+#[test]
+fn handle_emits_fields() {
+    let collector = Collector::default();
+    let res: Result<()> = Err(EzError::message("disk full")).loc(flc!());
+    let trace = res.clone().unwrap_err().trace_string();
+
+    tracing::subscriber::with_default(collector.clone(), || res.handle());
+
+    let fields = collector.0.lock().unwrap().clone();
+    let field = |name: &str| {
+        fields
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.clone())
+    };
+    assert_eq!(Some("Message".to_owned()), field("error.name"));
+    assert_eq!(Some("disk full".to_owned()), field("error.message"));
+    assert_eq!(Some(trace), field("error.trace"));
+    assert_eq!(
+        Some("Error Message: disk full".to_owned()),
+        field("message")
+    );
+}
+
+#[test]
+fn reported_error_has_no_trace() {
+    let collector = Collector::default();
+    let mut err = EzError::message("disk full");
+    err.add_frame(flc!());
+
+    tracing::subscriber::with_default(collector.clone(), || {
+        err.report();
+        collector.0.lock().unwrap().clear();
+        Err::<(), _>(err).handle();
+    });
+
+    let fields = collector.0.lock().unwrap().clone();
+    assert!(fields.iter().all(|(name, _)| name != "error.trace"));
+    assert!(fields.contains(&(
+        "message".to_owned(),
+        "Error Message (already reported)".to_owned()
+    )));
+}